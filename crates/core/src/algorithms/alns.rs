@@ -0,0 +1,624 @@
+//! Adaptive Large Neighborhood Search: repeatedly removes part of the plan
+//! with a destroy operator and rebuilds it with a repair operator, picking
+//! operators by weights that adapt to how often they pay off.
+
+use crate::algorithms::greedy;
+use crate::algorithms::solver::{Capabilities, Solver, StopToken};
+use crate::algorithms::simulated_annealing::{
+    build_itinerary, fit_around_appointments, itinerary_to_chromosome, Chromosome, DayPosition,
+};
+use crate::models::attraction::{Attraction, DayHours, Location};
+use crate::models::constraints::SolveParams;
+use crate::models::itinerary::{ConvergencePoint, Itinerary};
+use crate::models::travel::{HaversineProvider, TravelTimeProvider};
+use crate::utils::distance::haversine_distance;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+const MAX_ITERATIONS: usize = 1_000;
+const SEGMENT_LENGTH: usize = 50;      // iterations between weight updates
+const REACTION: f64 = 0.2;             // how fast weights follow recent scores
+const SCORE_NEW_BEST: f64 = 33.0;
+const SCORE_IMPROVED: f64 = 9.0;
+const SCORE_ACCEPTED: f64 = 13.0;
+const MAX_REMOVAL_FRACTION: f64 = 0.4;
+const WORST_REMOVAL_RANDOMNESS: f64 = 3.0;
+const REGRET_K: usize = 3;
+const NEIGHBORS: usize = 30;           // insert only next to one of these
+const TIME_WEIGHT: f64 = 1e-4;         // tie-break towards shorter days
+const START_ACCEPT_WORSE: f64 = 0.05;  // 5% worse accepted with p = 0.5 at first
+const END_TEMP_RATIO: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy)]
+enum Destroy {
+    Random,
+    Worst,
+    Related,
+    TimeWindow,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Repair {
+    Greedy,
+    Regret,
+}
+
+const DESTROY_OPS: [Destroy; 4] = [Destroy::Random, Destroy::Worst, Destroy::Related, Destroy::TimeWindow];
+const REPAIR_OPS: [Repair; 2] = [Repair::Greedy, Repair::Regret];
+
+/// Attraction indices visited on each day.
+type Routes = Vec<Vec<usize>>;
+
+/// Where a partial day leaves the traveller.
+#[derive(Debug, Clone, Copy)]
+struct DayState {
+    time: u32,
+    location: Location,
+    spent: f64,
+    attended: usize,  // appointments behind
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlnsConfig {
+    pub max_iterations: usize,
+}
+
+impl Default for AlnsConfig {
+    fn default() -> Self {
+        Self { max_iterations: MAX_ITERATIONS }
+    }
+}
+
+pub struct AlnsSolver;
+
+impl Solver for AlnsSolver {
+    type Config = AlnsConfig;
+
+    fn name(&self) -> &'static str {
+        "alns"
+    }
+
+    fn label(&self) -> &'static str {
+        "Adaptive Large Neighborhood Search"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities { stochastic: true, convergence_data: true, ..Default::default() }
+    }
+
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        config: &AlnsConfig,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+        stop: &StopToken,
+    ) -> Itinerary {
+        solve_with_config(attractions, params, config, seed, travel, stop)
+    }
+}
+
+pub fn solve(attractions: &[Attraction], params: &SolveParams, seed: u64) -> Itinerary {
+    solve_with_travel(attractions, params, seed, &HaversineProvider::default())
+}
+
+pub fn solve_with_travel(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+) -> Itinerary {
+    solve_with_config(attractions, params, &AlnsConfig::default(), seed, travel, &StopToken::new())
+}
+
+/// Like `solve_with_travel`, but with explicit settings; stops early with
+/// the best plan so far when `stop` fires.
+pub fn solve_with_config(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    config: &AlnsConfig,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+    stop: &StopToken,
+) -> Itinerary {
+    let start = Instant::now();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let search = Search::new(attractions, params, travel);
+
+    let greedy_solution = greedy::solve_with_travel(attractions, params, seed, travel);
+    let initial = search.routes_from(&itinerary_to_chromosome(&greedy_solution));
+    let (mut current, mut current_score) = search.evaluate(&initial);
+    let mut best = current.clone();
+    let mut best_score = current_score;
+
+    let mut destroy_weights = [1.0; DESTROY_OPS.len()];
+    let mut repair_weights = [1.0; REPAIR_OPS.len()];
+    let mut destroy_scores = [(0.0, 0usize); DESTROY_OPS.len()];
+    let mut repair_scores = [(0.0, 0usize); REPAIR_OPS.len()];
+
+    let start_temp = (START_ACCEPT_WORSE * best_score.max(0.1)) / std::f64::consts::LN_2;
+    let cooling = END_TEMP_RATIO.powf(1.0 / config.max_iterations.max(1) as f64);
+    let mut temperature = start_temp;
+    let mut convergence = Vec::new();
+
+    for iteration in 0..config.max_iterations {
+        if stop.should_stop() {
+            break;
+        }
+        let d = roulette(&destroy_weights, &mut rng);
+        let r = roulette(&repair_weights, &mut rng);
+
+        let mut candidate = current.clone();
+        search.destroy(DESTROY_OPS[d], &mut candidate, &mut rng);
+        search.repair(REPAIR_OPS[r], &mut candidate);
+        let (candidate, score) = search.evaluate(&candidate);
+
+        let reward = if score > best_score + 1e-9 {
+            best = candidate.clone();
+            best_score = score;
+            current = candidate;
+            current_score = score;
+            SCORE_NEW_BEST
+        } else if score > current_score + 1e-9 {
+            current = candidate;
+            current_score = score;
+            SCORE_IMPROVED
+        } else if rng.gen_range(0.0..1.0) < ((score - current_score) / temperature).exp() {
+            current = candidate;
+            current_score = score;
+            SCORE_ACCEPTED
+        } else {
+            0.0
+        };
+        destroy_scores[d].0 += reward;
+        destroy_scores[d].1 += 1;
+        repair_scores[r].0 += reward;
+        repair_scores[r].1 += 1;
+
+        if (iteration + 1) % SEGMENT_LENGTH == 0 {
+            update_weights(&mut destroy_weights, &mut destroy_scores);
+            update_weights(&mut repair_weights, &mut repair_scores);
+        }
+        if iteration % 50 == 0 {
+            convergence.push(ConvergencePoint {
+                iteration: iteration as u32,
+                satisfaction: best_score,
+                temperature,
+                mean_fitness: None,
+            });
+        }
+        temperature *= cooling;
+    }
+
+    let mut itinerary = build_itinerary(&search.chromosome(&best), attractions, params, travel);
+    itinerary.algorithm_used = "alns".to_string();
+    itinerary.computation_ms = start.elapsed().as_millis();
+    itinerary.convergence_data = Some(convergence);
+    itinerary.compute_totals();
+    itinerary
+}
+
+/// Index drawn with probability proportional to its weight.
+fn roulette(weights: &[f64], rng: &mut StdRng) -> usize {
+    let total: f64 = weights.iter().sum();
+    let mut pick = rng.gen_range(0.0..total);
+    for (i, w) in weights.iter().enumerate() {
+        if pick < *w {
+            return i;
+        }
+        pick -= w;
+    }
+    weights.len() - 1
+}
+
+fn update_weights(weights: &mut [f64], scores: &mut [(f64, usize)]) {
+    for (weight, (score, uses)) in weights.iter_mut().zip(scores.iter_mut()) {
+        if *uses > 0 {
+            *weight = (1.0 - REACTION) * *weight + REACTION * (*score / *uses as f64);
+            *weight = weight.max(0.05);
+        }
+        *score = 0.0;
+        *uses = 0;
+    }
+}
+
+struct Search<'a> {
+    attractions: &'a [Attraction],
+    params: &'a SolveParams,
+    travel: &'a dyn TravelTimeProvider,
+    hours: Vec<Vec<DayHours<'a>>>,  // [day][attraction]
+    index: HashMap<u32, usize>,
+    near: Vec<Vec<bool>>,           // [a][b]: b is among a's nearest
+    near_start: Vec<Vec<bool>>,     // [day][a]: a is among the nearest to where the day starts
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        attractions: &'a [Attraction],
+        params: &'a SolveParams,
+        travel: &'a dyn TravelTimeProvider,
+    ) -> Self {
+        let hours = (1..=params.num_days)
+            .map(|day| {
+                let date = params.date_for_day(day);
+                attractions.iter().map(|a| a.hours_on(date, &params.holidays)).collect()
+            })
+            .collect();
+        let index = attractions.iter().enumerate().map(|(i, a)| (a.id, i)).collect();
+
+        let nearest = |from: Location| {
+            let mut order: Vec<(f64, usize)> = attractions
+                .iter()
+                .enumerate()
+                .map(|(i, a)| (haversine_distance(from.lat, from.lng, a.location.lat, a.location.lng), i))
+                .collect();
+            order.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut near = vec![false; attractions.len()];
+            for &(_, i) in order.iter().take(NEIGHBORS + 1) {
+                near[i] = true;
+            }
+            near
+        };
+        let near = attractions.iter().map(|a| nearest(a.location)).collect();
+        let near_start = (1..=params.num_days).map(|day| nearest(params.day_start_location(day))).collect();
+
+        Self { attractions, params, travel, hours, index, near, near_start }
+    }
+
+    fn routes_from(&self, chromosome: &Chromosome) -> Routes {
+        let mut routes: Routes = chromosome
+            .iter()
+            .map(|day| day.iter().filter_map(|id| self.index.get(id).copied()).collect())
+            .collect();
+        routes.resize(self.hours.len(), Vec::new());
+        routes
+    }
+
+    fn chromosome(&self, routes: &Routes) -> Chromosome {
+        routes
+            .iter()
+            .map(|day| day.iter().map(|&i| self.attractions[i].id).collect())
+            .collect()
+    }
+
+    /// Decode the routes the same way the final itinerary is built, dropping
+    /// stops that do not fit, and score the result.
+    fn evaluate(&self, routes: &Routes) -> (Routes, f64) {
+        let itinerary = build_itinerary(&self.chromosome(routes), self.attractions, self.params, self.travel);
+        let kept: Chromosome = itinerary_to_chromosome(&itinerary);
+        let used_minutes: u32 = itinerary
+            .days
+            .iter()
+            .filter_map(|d| Some((d.day, d.return_arrival_time.or(d.visits.last().map(|v| v.departure_time))?)))
+            .map(|(day, done)| done.saturating_sub(self.params.day_start_time(day)))
+            .sum();
+        let satisfaction: f64 = itinerary.days.iter().map(|d| d.total_satisfaction).sum();
+
+        (self.routes_from(&kept), satisfaction - TIME_WEIGHT * used_minutes as f64)
+    }
+
+    fn day_start(&self, day: usize) -> DayState {
+        let start = DayPosition::start(self.params, day as u32 + 1);
+        DayState { time: start.time, location: start.location, spent: 0.0, attended: 0 }
+    }
+
+    /// State after visiting `idx` next on `day` with `money` to spend that
+    /// day, after an appointment if it does not fit before, or `None` if
+    /// the visit does not fit or leaves no way to end the day in time.
+    fn advance(&self, day: usize, state: &DayState, idx: usize, money: f64) -> Option<DayState> {
+        let attr = &self.attractions[idx];
+        if state.spent + attr.fee > money {
+            return None;
+        }
+        let d = day as u32 + 1;
+        let position = DayPosition { time: state.time, location: state.location, attended: state.attended };
+        let ((time, fare), at, appointment_legs) = fit_around_appointments(
+            self.params,
+            d,
+            position,
+            money - state.spent - attr.fee,
+            self.travel,
+            |at, fares| {
+                let max_fare = money - state.spent - attr.fee - fares;
+                let leg = self
+                    .params
+                    .transport
+                    .choose_leg(&at.location, &attr.location, Some(at.time), max_fare, self.travel)
+                    .ok_or(())?;
+                let start = self.hours[day][idx].earliest_visit_start(at.time + leg.travel_time).ok_or(())?;
+                let time = start + attr.duration;
+                let money_left = max_fare - leg.fare;
+                if !self.params.can_end_day(d, at.attended, &attr.location, time, money_left, self.travel) {
+                    return Err(());
+                }
+                Ok((time, leg.fare))
+            },
+        )
+        .ok()?;
+        let fares = fare + appointment_legs.iter().map(|(leg, _)| leg.fare).sum::<f64>();
+        Some(DayState { time, location: attr.location, spent: state.spent + attr.fee + fares, attended: at.attended })
+    }
+
+    /// End of the last visit if `stops` can be visited in order on `day`.
+    fn simulate_day(&self, day: usize, stops: &[usize], money: f64) -> Option<u32> {
+        stops
+            .iter()
+            .try_fold(self.day_start(day), |state, &idx| self.advance(day, &state, idx, money))
+            .map(|state| state.time)
+    }
+
+    fn destroy(&self, op: Destroy, routes: &mut Routes, rng: &mut StdRng) {
+        let visited: Vec<(usize, usize)> = routes
+            .iter()
+            .enumerate()
+            .flat_map(|(d, stops)| (0..stops.len()).map(move |p| (d, p)))
+            .collect();
+        if visited.is_empty() {
+            return;
+        }
+        let max_remove = ((visited.len() as f64 * MAX_REMOVAL_FRACTION).ceil() as usize).max(1);
+        let count = rng.gen_range(1..=max_remove);
+
+        let mut removed: Vec<usize> = match op {
+            Destroy::Random => {
+                let mut stops: Vec<usize> = visited.iter().map(|&(d, p)| routes[d][p]).collect();
+                for i in 0..count {
+                    let j = rng.gen_range(i..stops.len());
+                    stops.swap(i, j);
+                }
+                stops.truncate(count);
+                stops
+            }
+            Destroy::Worst => {
+                // Low preference for the time it takes, with some noise
+                let mut ranked: Vec<(f64, usize)> = visited
+                    .iter()
+                    .map(|&(d, p)| {
+                        let idx = routes[d][p];
+                        let without: Vec<usize> = routes[d].iter().copied().filter(|&i| i != idx).collect();
+                        let end_with = self.simulate_day(d, &routes[d], f64::INFINITY).unwrap_or(0);
+                        let end_without = self.simulate_day(d, &without, f64::INFINITY).unwrap_or(0);
+                        let saved = end_with.saturating_sub(end_without) as f64;
+                        (self.attractions[idx].preference / (saved + 1.0), idx)
+                    })
+                    .collect();
+                ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let mut removed = Vec::new();
+                while removed.len() < count && !ranked.is_empty() {
+                    let pick = (rng.gen_range(0.0f64..1.0).powf(WORST_REMOVAL_RANDOMNESS)
+                        * ranked.len() as f64) as usize;
+                    removed.push(ranked.remove(pick.min(ranked.len() - 1)).1);
+                }
+                removed
+            }
+            Destroy::Related => {
+                // Stops geographically closest to a random seed stop
+                let (d, p) = visited[rng.gen_range(0..visited.len())];
+                let seed = self.attractions[routes[d][p]].location;
+                let mut stops: Vec<(f64, usize)> = visited
+                    .iter()
+                    .map(|&(d, p)| {
+                        let loc = self.attractions[routes[d][p]].location;
+                        (haversine_distance(seed.lat, seed.lng, loc.lat, loc.lng), routes[d][p])
+                    })
+                    .collect();
+                stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+                stops.into_iter().take(count).map(|(_, idx)| idx).collect()
+            }
+            Destroy::TimeWindow => {
+                // Stops whose opening hours are most like a random seed stop's
+                let (d, p) = visited[rng.gen_range(0..visited.len())];
+                let seed = self.attractions[routes[d][p]].hours();
+                let (seed_open, seed_close) = (seed.opens_at().unwrap_or(0), seed.closes_at().unwrap_or(0));
+                let mut stops: Vec<(u32, usize)> = visited
+                    .iter()
+                    .map(|&(d, p)| {
+                        let hours = self.attractions[routes[d][p]].hours();
+                        let open = hours.opens_at().unwrap_or(0);
+                        let close = hours.closes_at().unwrap_or(0);
+                        (open.abs_diff(seed_open) + close.abs_diff(seed_close), routes[d][p])
+                    })
+                    .collect();
+                stops.sort_by_key(|s| s.0);
+                stops.into_iter().take(count).map(|(_, idx)| idx).collect()
+            }
+        };
+
+        removed.sort_unstable();
+        for day in routes.iter_mut() {
+            day.retain(|idx| removed.binary_search(idx).is_err());
+        }
+    }
+
+    /// Cheapest feasible insertion of every unassigned attraction into
+    /// `day`, as (added minutes, position), with `money` to spend that day.
+    /// Only positions next to one of the attraction's near neighbours are
+    /// tried.
+    fn day_insertions(&self, day: usize, stops: &[usize], money: f64, assigned: &[bool]) -> Vec<Option<(u32, usize)>> {
+        // States before each insertion position, and the day's current end
+        let mut prefix = vec![self.day_start(day)];
+        for &idx in stops {
+            match self.advance(day, prefix.last().unwrap(), idx, money) {
+                Some(state) => prefix.push(state),
+                None => break,
+            }
+        }
+        let end = prefix.last().unwrap().time;
+
+        (0..self.attractions.len())
+            .map(|idx| {
+                if assigned[idx] || self.hours[day][idx].is_closed() {
+                    return None;
+                }
+                let day_end = self.params.day_end(day as u32 + 1);
+                (0..prefix.len())
+                    .filter(|&pos| {
+                        let after_prev = match pos {
+                            0 => self.near_start[day][idx],
+                            _ => self.near[stops[pos - 1]][idx],
+                        };
+                        let before_next = stops.get(pos).is_some_and(|&next| self.near[next][idx]);
+                        (stops.is_empty() || after_prev || before_next)
+                            && prefix[pos].time + self.attractions[idx].duration <= day_end
+                    })
+                    .filter_map(|pos| {
+                        let state = self.advance(day, &prefix[pos], idx, money)?;
+                        let state = stops[pos..]
+                            .iter()
+                            .try_fold(state, |s, &next| self.advance(day, &s, next, money))?;
+                        Some((state.time.saturating_sub(end), pos))
+                    })
+                    .min_by_key(|&(added, _)| added)
+            })
+            .collect()
+    }
+
+    /// Entrance fees of a day's stops; fares are left to the final decode.
+    fn fees(&self, stops: &[usize]) -> f64 {
+        stops.iter().map(|&i| self.attractions[i].fee).sum()
+    }
+
+    fn repair(&self, op: Repair, routes: &mut Routes) {
+        let mut assigned = vec![false; self.attractions.len()];
+        for &idx in routes.iter().flatten() {
+            assigned[idx] = true;
+        }
+
+        // insertions[day][idx]; only the day that changed is recomputed
+        let mut spent: f64 = routes.iter().map(|d| self.fees(d)).sum();
+        let mut insertions: Vec<Vec<Option<(u32, usize)>>> = routes
+            .iter()
+            .enumerate()
+            .map(|(day, stops)| {
+                let money = self.params.total_budget - spent + self.fees(stops);
+                self.day_insertions(day, stops, money, &assigned)
+            })
+            .collect();
+
+        loop {
+            let money_left = self.params.total_budget - spent;
+
+            // (priority, idx, day, pos); higher priority is inserted first
+            let mut choice: Option<(f64, usize, usize, usize)> = None;
+            for idx in (0..self.attractions.len()).filter(|&i| !assigned[i]) {
+                let attr = &self.attractions[idx];
+                if attr.fee > money_left {
+                    continue;
+                }
+                let mut options: Vec<(u32, usize, usize)> = insertions
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(day, per_day)| per_day[idx].map(|(added, pos)| (added, day, pos)))
+                    .collect();
+                options.sort_by_key(|o| o.0);
+                let Some(&(added, day, pos)) = options.first() else {
+                    continue;
+                };
+
+                let priority = match op {
+                    Repair::Greedy => attr.preference / (added as f64 + 1.0),
+                    Repair::Regret => {
+                        // Cost per unit of preference; missing options count
+                        // as a full day
+                        let cost = |o: Option<&(u32, usize, usize)>| {
+                            o.map_or(self.params.daily_time_budget, |o| o.0) as f64
+                                / attr.preference.max(1e-6)
+                        };
+                        let first = cost(options.first());
+                        (1..REGRET_K).map(|k| cost(options.get(k)) - first).sum::<f64>()
+                            + attr.preference / (added as f64 + 1.0)
+                    }
+                };
+                if choice.is_none_or(|c| priority > c.0) {
+                    choice = Some((priority, idx, day, pos));
+                }
+            }
+
+            let Some((_, idx, day, pos)) = choice else {
+                break;
+            };
+            routes[day].insert(pos, idx);
+            assigned[idx] = true;
+            spent += self.attractions[idx].fee;
+
+            let money = self.params.total_budget - spent + self.fees(&routes[day]);
+            insertions[day] = self.day_insertions(day, &routes[day], money, &assigned);
+            for per_day in insertions.iter_mut() {
+                per_day[idx] = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::attraction::{Category, TimeWindow};
+    use crate::utils::verifier::{verify_itinerary, verify_opening_hours};
+
+    fn create_test_attraction(id: u32, lat: f64, lng: f64, preference: f64) -> Attraction {
+        Attraction {
+            id,
+            name: format!("Attraction {}", id),
+            location: Location { lat, lng },
+            windows: vec![TimeWindow::new(540, 1020)],
+            duration: 60,
+            fee: 5.0,
+            preference,
+            category: Category::Museum,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
+        }
+    }
+
+    fn grid(n: u32) -> Vec<Attraction> {
+        (0..n)
+            .map(|i| {
+                let lat = 13.70 + 0.012 * (i % 5) as f64;
+                let lng = 100.46 + 0.012 * (i / 5) as f64;
+                create_test_attraction(i + 1, lat, lng, 0.3 + 0.05 * (i % 13) as f64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_alns_is_valid_and_no_worse_than_greedy() {
+        let attractions = grid(25);
+        let params = SolveParams { daily_time_budget: 420, ..Default::default() };
+
+        let result = solve(&attractions, &params, 42);
+        assert!(verify_itinerary(&result, &params).is_ok());
+        assert!(verify_opening_hours(&result, &attractions, &params).is_ok());
+
+        let baseline = greedy::solve(&attractions, &params, 42);
+        assert!(result.total_satisfaction >= baseline.total_satisfaction - 1e-9);
+    }
+
+    #[test]
+    fn test_alns_is_deterministic_per_seed() {
+        let attractions = grid(20);
+        let params = SolveParams::default();
+
+        let a = solve(&attractions, &params, 7);
+        let b = solve(&attractions, &params, 7);
+        assert_eq!(itinerary_to_chromosome(&a), itinerary_to_chromosome(&b));
+    }
+
+    #[test]
+    fn test_repair_fills_empty_plan() {
+        let attractions = grid(6);
+        let params = SolveParams::default();
+        let travel = HaversineProvider::default();
+        let search = Search::new(&attractions, &params, &travel);
+
+        let mut routes: Routes = vec![Vec::new(); 2];
+        search.repair(Repair::Regret, &mut routes);
+        assert_eq!(routes.iter().map(Vec::len).sum::<usize>(), 6);
+    }
+}
@@ -0,0 +1,680 @@
+//! Exact solver for small instances: depth-first branch and bound over
+//! day-by-day routes, pruned with LP-relaxation bounds. Schedules follow the
+//! same rules as the simulated annealing decoder (mode choice, waiting,
+//! every visit leaving time to reach the day's end point), so the result
+//! bounds what the heuristics can reach. The time bound assumes congestion never makes
+//! a leg faster than free flow.
+
+use crate::algorithms::greedy;
+use crate::algorithms::solver::{Capabilities, Solver, StopToken};
+use crate::models::attraction::{Attraction, DayHours, Location};
+use crate::models::constraints::SolveParams;
+use crate::models::itinerary::{DayPlan, Itinerary, Visit};
+use crate::models::transport::Leg;
+use crate::models::travel::{HaversineProvider, TravelTimeProvider};
+use crate::utils::verifier::find_violations;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Search nodes explored before giving up on proving optimality.
+pub const MAX_NODES: u64 = 2_000_000;
+
+/// Largest dataset the server and experiments run the exact solver on.
+pub const MAX_EXACT_ATTRACTIONS: usize = 30;
+
+const EPS: f64 = 1e-9;
+const STOP_CHECK_INTERVAL: u64 = 1024;  // nodes between `StopToken` checks
+
+/// (day, visited set, last stop, first stop of the day, appointments behind)
+type StateKey = (usize, u64, usize, usize, usize);
+
+pub struct ExactSolution {
+    pub itinerary: Itinerary,
+    /// No feasible itinerary scores more than this.
+    pub upper_bound: f64,
+    /// Whether the search finished, i.e. `itinerary` is optimal.
+    pub proven_optimal: bool,
+    pub nodes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExactConfig {
+    pub max_nodes: u64,
+}
+
+impl Default for ExactConfig {
+    fn default() -> Self {
+        Self { max_nodes: MAX_NODES }
+    }
+}
+
+pub struct ExactSolver;
+
+impl Solver for ExactSolver {
+    type Config = ExactConfig;
+
+    fn name(&self) -> &'static str {
+        "branch_and_bound"
+    }
+
+    fn label(&self) -> &'static str {
+        "Branch and Bound (exact)"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            exact: true,
+            max_attractions: Some(MAX_EXACT_ATTRACTIONS),
+            ..Default::default()
+        }
+    }
+
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        config: &ExactConfig,
+        _seed: u64,
+        travel: &dyn TravelTimeProvider,
+        stop: &StopToken,
+    ) -> Itinerary {
+        solve_exact(attractions, params, travel, config.max_nodes, stop).itinerary
+    }
+}
+
+pub fn solve(attractions: &[Attraction], params: &SolveParams, seed: u64) -> Itinerary {
+    solve_with_travel(attractions, params, seed, &HaversineProvider::default())
+}
+
+pub fn solve_with_travel(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    _seed: u64,
+    travel: &dyn TravelTimeProvider,
+) -> Itinerary {
+    solve_exact(attractions, params, travel, MAX_NODES, &StopToken::new()).itinerary
+}
+
+/// Best itinerary found within `max_nodes` search nodes (or before `stop`
+/// fires), with a bound on the optimum.
+pub fn solve_exact(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+    max_nodes: u64,
+    stop: &StopToken,
+) -> ExactSolution {
+    let start = Instant::now();
+
+    // Start from the greedy plan so weak branches are cut early, unless it
+    // breaks a rule (e.g. spends the fare a later day needs)
+    let incumbent = greedy::solve_with_travel(attractions, params, 0, travel);
+
+    let mut search = Search::new(attractions, params, travel, max_nodes, stop);
+    search.best = if find_violations(&incumbent, attractions, params, travel).is_empty() {
+        incumbent.total_satisfaction
+    } else {
+        f64::NEG_INFINITY
+    };
+    search.branch(0, params.day_start_time(1), params.day_start_location(1), 0, 0.0, 0.0);
+
+    let proven_optimal = !search.interrupted && search.nodes <= max_nodes;
+    let upper_bound = if proven_optimal {
+        search.best.max(0.0)
+    } else {
+        search.best.max(search.cut_bound)
+    };
+
+    let mut itinerary = match &search.best_route {
+        Some((route, attended)) => search.build_itinerary(route, attended),
+        None => incumbent,
+    };
+    itinerary.algorithm_used = "branch_and_bound".to_string();
+    itinerary.computation_ms = start.elapsed().as_millis();
+    itinerary.convergence_data = None;
+
+    ExactSolution {
+        itinerary,
+        upper_bound,
+        proven_optimal,
+        nodes: search.nodes.min(max_nodes),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Stop {
+    idx: usize,
+    leg: Leg,
+    arrival: u32,
+    start: u32,
+}
+
+/// An appointment reached by `leg`, leaving at `left_at` after the first
+/// `after` stops of its day.
+#[derive(Debug, Clone, Copy)]
+struct Attendance {
+    after: usize,
+    leg: Leg,
+    left_at: u32,
+}
+
+/// Each day's stops and the appointments attended between them.
+type Route = (Vec<Vec<Stop>>, Vec<Vec<Attendance>>);
+
+struct Search<'a> {
+    attractions: &'a [Attraction],
+    params: &'a SolveParams,
+    travel: &'a dyn TravelTimeProvider,
+    hours: Vec<Vec<DayHours<'a>>>,       // [day][attraction]
+    last_open_day: Vec<Option<usize>>,   // per attraction
+    min_inbound: Vec<u32>,               // fastest leg into each attraction
+    interchangeable_days: bool,
+    monotone_legs: bool,
+    visited: Vec<bool>,
+    visited_mask: u64,
+    /// Non-dominated (time, cost) pairs seen per state; only kept for up
+    /// to 64 attractions.
+    seen: HashMap<StateKey, Vec<(u32, f64)>>,
+    route: Vec<Vec<Stop>>,
+    attended: Vec<Vec<Attendance>>,
+    best: f64,
+    best_route: Option<Route>,
+    nodes: u64,
+    max_nodes: u64,
+    stop: &'a StopToken,
+    interrupted: bool,
+    cut_bound: f64,  // best bound among nodes left unexplored
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        attractions: &'a [Attraction],
+        params: &'a SolveParams,
+        travel: &'a dyn TravelTimeProvider,
+        max_nodes: u64,
+        stop: &'a StopToken,
+    ) -> Self {
+        let hours: Vec<Vec<DayHours>> = (1..=params.num_days)
+            .map(|day| {
+                let date = params.date_for_day(day);
+                attractions.iter().map(|a| a.hours_on(date, &params.holidays)).collect()
+            })
+            .collect();
+        let last_open_day = (0..attractions.len())
+            .map(|i| (0..hours.len()).rev().find(|&d| !hours[d][i].is_closed()))
+            .collect();
+
+        // Visits are reached from where a day starts, another attraction or
+        // an appointment
+        let starts: Vec<Location> = (1..=params.num_days)
+            .map(|day| params.day_start_location(day))
+            .chain((1..=params.num_days).flat_map(|day| params.appointments(day).iter().map(|a| a.location())))
+            .collect();
+        let min_inbound = attractions
+            .iter()
+            .map(|to| {
+                starts
+                    .iter()
+                    .copied()
+                    .chain(attractions.iter().filter(|a| a.id != to.id).map(|a| a.location))
+                    .flat_map(|from| {
+                        params.transport.modes.iter().filter_map(move |m| {
+                            m.leg(&from, &to.location, None, travel).map(|leg| leg.travel_time)
+                        })
+                    })
+                    .min()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        // Days can be reordered freely when they share the same hours,
+        // settings and end points, have no appointments, and fares cannot
+        // make leg choice depend on the money left
+        let fare_free = params
+            .transport
+            .modes
+            .iter()
+            .all(|m| m.base_fare == 0.0 && m.fare_per_km == 0.0);
+        // Leaving earlier with more money left only ever helps when there is
+        // a single mode running all day
+        let monotone_legs = fare_free
+            && params.transport.modes.len() == 1
+            && params.transport.modes[0].available_from == 0
+            && params.transport.modes[0].available_until >= 1440;
+        let same_hours = hours.windows(2).all(|pair| {
+            pair[0].iter().zip(&pair[1]).all(|(a, b)| a.windows() == b.windows())
+        });
+        let settings: Vec<_> = (1..=params.num_days)
+            .map(|day| {
+                let start = params.day_start_location(day);
+                let end = params.day_end_location(day).map(|end| (end.lat, end.lng));
+                ((start.lat, start.lng), end, params.day_start_time(day), params.day_time_budget(day))
+            })
+            .collect();
+        let alike_days = settings.windows(2).all(|pair| pair[0] == pair[1])
+            && (1..=params.num_days).all(|day| params.appointments(day).is_empty());
+
+        Self {
+            attractions,
+            params,
+            travel,
+            hours,
+            last_open_day,
+            min_inbound,
+            interchangeable_days: fare_free && same_hours && alike_days,
+            monotone_legs,
+            visited: vec![false; attractions.len()],
+            visited_mask: 0,
+            seen: HashMap::new(),
+            route: vec![Vec::new(); params.num_days as usize],
+            attended: vec![Vec::new(); params.num_days as usize],
+            best: 0.0,
+            best_route: None,
+            nodes: 0,
+            max_nodes,
+            stop,
+            interrupted: false,
+            cut_bound: 0.0,
+        }
+    }
+
+    fn branch(&mut self, day: usize, time: u32, location: Location, attended: usize, cost: f64, value: f64) {
+        if day >= self.hours.len() {
+            return;
+        }
+        let d = day as u32 + 1;
+        self.nodes += 1;
+
+        if value > self.best + EPS && self.can_finish_trip(day, time, location, attended, cost) {
+            self.best = value;
+            self.best_route = Some((self.route.clone(), self.attended.clone()));
+        }
+
+        let bound = value + self.remaining_bound(day, time, cost);
+        if bound <= self.best + EPS {
+            return;
+        }
+        if !self.interrupted && self.nodes % STOP_CHECK_INTERVAL == 1 && self.stop.should_stop() {
+            self.interrupted = true;
+        }
+        if self.interrupted || self.nodes > self.max_nodes {
+            self.cut_bound = self.cut_bound.max(bound);
+            return;
+        }
+
+        // With interchangeable days, only consider routes whose days are
+        // ordered by their first attraction (and empty days come last)
+        let min_first = match (self.interchangeable_days, self.route[day].is_empty(), day) {
+            (true, true, d) if d > 0 => match self.route[d - 1].first() {
+                Some(prev) => prev.idx + 1,
+                None => return,
+            },
+            _ => 0,
+        };
+
+        // Visit another attraction today, most preferred first
+        let mut children = Vec::new();
+        for (idx, attr) in self.attractions.iter().enumerate().skip(min_first) {
+            if self.visited[idx] || cost + attr.fee > self.params.total_budget {
+                continue;
+            }
+            let max_fare = self.params.total_budget - cost - attr.fee;
+            let leg = match self.params.transport.choose_leg(
+                &location,
+                &attr.location,
+                Some(time),
+                max_fare,
+                self.travel,
+            ) {
+                Some(leg) => leg,
+                None => continue,
+            };
+            let arrival = time + leg.travel_time;
+            let start = match self.hours[day][idx].earliest_visit_start(arrival) {
+                Some(t) => t,
+                None => continue,
+            };
+            let departure = start + attr.duration;
+            if !self.params.can_end_day(d, attended, &attr.location, departure, max_fare - leg.fare, self.travel) {
+                continue;
+            }
+            children.push(Stop { idx, leg, arrival, start });
+        }
+        children.sort_by(|a, b| {
+            self.attractions[b.idx]
+                .preference
+                .total_cmp(&self.attractions[a.idx].preference)
+                .then(a.start.cmp(&b.start))
+        });
+
+        for stop in children {
+            let attr = &self.attractions[stop.idx];
+            let departure = stop.start + attr.duration;
+            let next_cost = cost + attr.fee + stop.leg.fare;
+            if self.dominated(day, stop.idx, attended, departure, next_cost) {
+                continue;
+            }
+
+            self.visit(stop.idx, true);
+            self.route[day].push(stop);
+            self.branch(day, departure, attr.location, attended, next_cost, value + attr.preference);
+            self.route[day].pop();
+            self.visit(stop.idx, false);
+        }
+
+        // Or go on to the next appointment
+        let money = self.params.total_budget - cost;
+        if let Some((leg, free_at, at)) = self.params.attend_next(d, attended, &location, time, money, self.travel) {
+            let after = self.route[day].len();
+            self.attended[day].push(Attendance { after, leg, left_at: time });
+            self.branch(day, free_at, at, attended + 1, cost + leg.fare, value);
+            self.attended[day].pop();
+        }
+
+        // Or end the day here, paying for the rest of its legs, if that gets
+        // the traveller to its end in time
+        if !self.params.can_end_day(d, attended, &location, time, money, self.travel) {
+            return;
+        }
+        let closing_fares = self
+            .params
+            .finish_day(d, attended, &location, time, money, self.travel)
+            .map_or(0.0, |(fares, _)| fares);
+        self.branch(
+            day + 1,
+            self.params.day_start_time(d + 1),
+            self.params.day_start_location(d + 1),
+            0,
+            cost + closing_fares,
+            value,
+        );
+    }
+
+    /// Whether ending `day` here and leaving the remaining days empty is a
+    /// trip that gets everywhere in time and within budget.
+    fn can_finish_trip(&self, day: usize, time: u32, location: Location, attended: usize, cost: f64) -> bool {
+        let (mut day, mut time, mut location, mut attended, mut cost) = (day as u32 + 1, time, location, attended, cost);
+        while day <= self.params.num_days {
+            let money = self.params.total_budget - cost;
+            if !self.params.can_end_day(day, attended, &location, time, money, self.travel) {
+                return false;
+            }
+            let (fares, _) = self.params.finish_day(day, attended, &location, time, money, self.travel).unwrap_or_default();
+            day += 1;
+            (time, location, attended, cost) =
+                (self.params.day_start_time(day), self.params.day_start_location(day), 0, cost + fares);
+        }
+        true
+    }
+
+    fn visit(&mut self, idx: usize, visited: bool) {
+        self.visited[idx] = visited;
+        if idx < 64 {
+            self.visited_mask ^= 1 << idx;
+        }
+    }
+
+    /// Whether the same attractions were already visited by this day, ending
+    /// at `last` no later and no more expensively. Satisfaction depends only
+    /// on the set, so such a state cannot do better. Records the state
+    /// otherwise.
+    fn dominated(&mut self, day: usize, last: usize, attended: usize, time: u32, cost: f64) -> bool {
+        if self.attractions.len() > 64 {
+            return false;
+        }
+        let first = self.route[day].first().map_or(last, |s| s.idx);
+        let key = (day, self.visited_mask | (1 << last), last, first, attended);
+        let covers = |t: u32, c: f64, time: u32, cost: f64, monotone: bool| {
+            if monotone {
+                t <= time && c <= cost + EPS
+            } else {
+                t == time && (c - cost).abs() <= EPS
+            }
+        };
+
+        let monotone = self.monotone_legs;
+        let states = self.seen.entry(key).or_default();
+        if states.iter().any(|&(t, c)| covers(t, c, time, cost, monotone)) {
+            return true;
+        }
+        states.retain(|&(t, c)| !covers(time, cost, t, c, monotone));
+        states.push((time, cost));
+        false
+    }
+
+    /// Upper bound on the preference still collectable: the smaller of the
+    /// LP relaxations of the money and time knapsacks over attractions that
+    /// are unvisited, affordable and open on a remaining day. Each visit
+    /// takes at least its duration plus the fastest leg into it.
+    fn remaining_bound(&self, day: usize, time: u32, cost: f64) -> f64 {
+        let money = self.params.total_budget - cost;
+        let d = day as u32 + 1;
+        let later_minutes: u32 = (d + 1..=self.params.num_days).map(|later| self.params.day_time_budget(later)).sum();
+        let minutes = self.params.day_end(d).saturating_sub(time) as f64 + later_minutes as f64;
+
+        let items: Vec<(f64, f64, f64)> = self
+            .attractions
+            .iter()
+            .enumerate()
+            .filter(|(idx, attr)| {
+                !self.visited[*idx]
+                    && attr.fee <= money
+                    && match self.last_open_day[*idx] {
+                        Some(last) if last > day => true,
+                        Some(last) if last == day => self.hours[day][*idx]
+                            .closes_at()
+                            .is_some_and(|close| close >= time + attr.duration),
+                        _ => false,
+                    }
+            })
+            .map(|(idx, attr)| {
+                let minutes = attr.duration + self.min_inbound[idx];
+                (attr.preference, attr.fee, minutes as f64)
+            })
+            .collect();
+
+        let by_money = fractional_knapsack(items.iter().map(|&(p, fee, _)| (p, fee)), money);
+        let by_time = fractional_knapsack(items.iter().map(|&(p, _, d)| (p, d)), minutes);
+        by_money.min(by_time)
+    }
+
+    fn build_itinerary(&self, route: &[Vec<Stop>], attended: &[Vec<Attendance>]) -> Itinerary {
+        let mut spent = 0.0;
+        let days = route
+            .iter()
+            .zip(attended)
+            .enumerate()
+            .map(|(day_idx, (stops, attended))| {
+                let day = day_idx as u32 + 1;
+                let visits: Vec<Visit> = stops
+                    .iter()
+                    .map(|stop| {
+                        let attr = &self.attractions[stop.idx];
+                        Visit {
+                            attraction_id: attr.id,
+                            attraction_name: attr.name.clone(),
+                            arrival_time: stop.start,
+                            departure_time: stop.start + attr.duration,
+                            fee: attr.fee,
+                            preference: attr.preference,
+                            category: format!("{:?}", attr.category),
+                            leg: stop.leg,
+                            wait_minutes: stop.start - stop.arrival,
+                        }
+                    })
+                    .collect();
+                let total_fare: f64 = visits.iter().map(|v| v.leg.fare).sum();
+
+                let appointments = self.params.appointments(day);
+                let (from, left_at) = match (stops.last(), attended.last()) {
+                    (_, Some(last)) if last.after == stops.len() => {
+                        let appointment = &appointments[attended.len() - 1];
+                        (appointment.location(), appointment.end)
+                    }
+                    (Some(stop), _) => {
+                        let attr = &self.attractions[stop.idx];
+                        (attr.location, stop.start + attr.duration)
+                    }
+                    (None, _) => (self.params.day_start_location(day), self.params.day_start_time(day)),
+                };
+
+                let mut plan = DayPlan {
+                    day,
+                    appointments: Vec::new(),
+                    total_travel_time: visits.iter().map(|v| v.leg.travel_time).sum(),
+                    total_cost: visits.iter().map(|v| v.fee).sum::<f64>() + total_fare,
+                    total_fare,
+                    total_satisfaction: visits.iter().map(|v| v.preference).sum(),
+                    visits,
+                    return_leg: None,
+                    return_arrival_time: None,
+                };
+                for attendance in attended {
+                    plan.attend(self.params, attendance.leg, attendance.left_at);
+                }
+                let money_left = self.params.total_budget - spent - plan.total_cost;
+                plan.close(self.params, &from, left_at, money_left, self.travel);
+                spent += plan.total_cost;
+                plan
+            })
+            .collect();
+
+        let mut itinerary = Itinerary::empty("branch_and_bound".to_string());
+        itinerary.days = days;
+        itinerary.compute_totals();
+        itinerary
+    }
+}
+
+/// Value of the LP relaxation of a 0/1 knapsack with `(value, weight)` items.
+fn fractional_knapsack(items: impl Iterator<Item = (f64, f64)>, capacity: f64) -> f64 {
+    let mut items: Vec<(f64, f64)> = items.collect();
+    items.sort_by(|a, b| (b.0 / b.1).total_cmp(&(a.0 / a.1)));  // free items first
+
+    let mut remaining = capacity;
+    let mut total = 0.0;
+    for (value, weight) in items {
+        if weight <= remaining {
+            total += value;
+            remaining -= weight;
+        } else {
+            total += value * remaining / weight;
+            break;
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::simulated_annealing;
+    use crate::models::attraction::{Category, TimeWindow};
+    use crate::models::constraints::DayOverride;
+    use crate::models::transport::TransportOptions;
+    use crate::utils::verifier::{verify_itinerary, verify_opening_hours};
+    use std::collections::HashMap;
+
+    fn create_test_attraction(id: u32, lat: f64, lng: f64, preference: f64) -> Attraction {
+        Attraction {
+            id,
+            name: format!("Attraction {}", id),
+            location: Location { lat, lng },
+            windows: vec![TimeWindow::new(540, 1020)],
+            duration: 90,
+            fee: 10.0,
+            preference,
+            category: Category::Museum,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
+        }
+    }
+
+    fn instance() -> Vec<Attraction> {
+        (0..8)
+            .map(|i| {
+                let lat = 13.72 + 0.01 * (i % 4) as f64;
+                let lng = 100.48 + 0.015 * (i / 2) as f64;
+                create_test_attraction(i + 1, lat, lng, 0.4 + 0.07 * i as f64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_is_valid_and_beats_heuristics() {
+        let attractions = instance();
+        let params = SolveParams {
+            num_days: 1,
+            daily_time_budget: 360,
+            total_budget: 45.0,
+            ..Default::default()
+        };
+        let travel = HaversineProvider::default();
+
+        let exact = solve_exact(&attractions, &params, &travel, MAX_NODES, &StopToken::new());
+        assert!(exact.proven_optimal);
+        assert!(verify_itinerary(&exact.itinerary, &params).is_ok());
+        assert!(verify_opening_hours(&exact.itinerary, &attractions, &params).is_ok());
+        assert!((exact.itinerary.total_satisfaction - exact.upper_bound).abs() < 1e-9);
+
+        for heuristic in [
+            greedy::solve(&attractions, &params, 42),
+            simulated_annealing::solve(&attractions, &params, 42),
+        ] {
+            assert!(heuristic.total_satisfaction <= exact.upper_bound + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_node_limit_reports_bound() {
+        let attractions = instance();
+        let params = SolveParams {
+            num_days: 2,
+            daily_time_budget: 240,
+            total_budget: 45.0,
+            ..Default::default()
+        };
+
+        let exact = solve_exact(&attractions, &params, &HaversineProvider::default(), 5, &StopToken::new());
+        assert!(!exact.proven_optimal);
+        assert!(exact.upper_bound >= exact.itinerary.total_satisfaction);
+        assert!(verify_itinerary(&exact.itinerary, &params).is_ok());
+    }
+
+    #[test]
+    fn test_exact_keeps_the_fare_for_the_last_day() {
+        let attractions = instance();
+        let travel = HaversineProvider::default();
+        // The last night is in a hotel across town, only reached by taxi
+        let bangkok = TransportOptions::bangkok();
+        let transport = TransportOptions { modes: vec![bangkok.modes[1].clone()], ..bangkok };
+        for total_budget in [25.0, 30.0, 35.0, 40.0] {
+            let params = SolveParams {
+                num_days: 2,
+                daily_time_budget: 300,
+                total_budget,
+                transport: transport.clone(),
+                day_overrides: vec![DayOverride {
+                    day: 2,
+                    hotel_lat: Some(13.9000),
+                    hotel_lng: Some(100.7000),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+
+            let exact = solve_exact(&attractions, &params, &travel, MAX_NODES, &StopToken::new());
+            let violations = find_violations(&exact.itinerary, &attractions, &params, &travel);
+            assert!(violations.is_empty(), "budget {}: {:?}", total_budget, violations);
+        }
+    }
+
+    #[test]
+    fn test_fractional_knapsack() {
+        let items = [(3.0, 2.0), (1.0, 0.0), (2.0, 4.0)];
+        assert_eq!(fractional_knapsack(items.into_iter(), 4.0), 5.0);
+    }
+}
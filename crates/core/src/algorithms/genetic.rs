@@ -0,0 +1,332 @@
+//! Memetic genetic algorithm over the same day-by-day `Chromosome` as
+//! simulated annealing: ordered crossover across days, the SA moves as
+//! mutation, and a repair step that drops stops that do not fit and tops
+//! days up with well-liked attractions.
+
+use crate::algorithms::greedy;
+use crate::algorithms::solver::{Capabilities, Solver, StopToken};
+use crate::algorithms::simulated_annealing::{
+    build_itinerary, evaluate_fitness, itinerary_to_chromosome, mutate, Chromosome, Penalties,
+};
+use crate::models::attraction::Attraction;
+use crate::models::constraints::SolveParams;
+use crate::models::itinerary::{ConvergencePoint, Itinerary};
+use crate::models::travel::{HaversineProvider, TravelTimeProvider};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Instant;
+
+const POPULATION_SIZE: usize = 40;
+const GENERATIONS: usize = 150;
+const TOURNAMENT_SIZE: usize = 3;
+const ELITE_COUNT: usize = 2;
+const CROSSOVER_RATE: f64 = 0.9;
+const MUTATION_RATE: f64 = 0.3;
+const REPAIR_INSERTIONS: usize = 3;  // unvisited attractions tried per repair
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GaConfig {
+    pub population_size: usize,
+    pub generations: usize,
+    pub crossover_rate: f64,
+    pub mutation_rate: f64,
+}
+
+impl Default for GaConfig {
+    fn default() -> Self {
+        Self {
+            population_size: POPULATION_SIZE,
+            generations: GENERATIONS,
+            crossover_rate: CROSSOVER_RATE,
+            mutation_rate: MUTATION_RATE,
+        }
+    }
+}
+
+pub struct GeneticSolver;
+
+impl Solver for GeneticSolver {
+    type Config = GaConfig;
+
+    fn name(&self) -> &'static str {
+        "genetic"
+    }
+
+    fn label(&self) -> &'static str {
+        "Genetic Algorithm"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities { stochastic: true, convergence_data: true, ..Default::default() }
+    }
+
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        config: &GaConfig,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+        stop: &StopToken,
+    ) -> Itinerary {
+        solve_with_config(attractions, params, config, seed, travel, stop)
+    }
+}
+
+pub fn solve(attractions: &[Attraction], params: &SolveParams, seed: u64) -> Itinerary {
+    solve_with_travel(attractions, params, seed, &HaversineProvider::default())
+}
+
+pub fn solve_with_travel(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+) -> Itinerary {
+    solve_with_config(attractions, params, &GaConfig::default(), seed, travel, &StopToken::new())
+}
+
+/// Like `solve_with_travel`, but with explicit settings; stops early with
+/// the best plan so far when `stop` fires.
+pub fn solve_with_config(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    config: &GaConfig,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+    stop: &StopToken,
+) -> Itinerary {
+    let population_size = config.population_size.max(ELITE_COUNT + 1);
+    let crossover_rate = config.crossover_rate.clamp(0.0, 1.0);
+    let mutation_rate = config.mutation_rate.clamp(0.0, 1.0);
+    let start = Instant::now();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let ga = Genetic { attractions, params, travel };
+
+    // Seed the population with the greedy plan, variations of it and
+    // random plans
+    let greedy_solution = greedy::solve_with_travel(attractions, params, seed, travel);
+    let mut seed_plan = itinerary_to_chromosome(&greedy_solution);
+    seed_plan.resize(params.num_days as usize, Vec::new());
+
+    let mut population = vec![ga.individual(seed_plan.clone())];
+    while population.len() < population_size {
+        let chromosome = if rng.gen_bool(0.5) {
+            let mut variant = seed_plan.clone();
+            for _ in 0..rng.gen_range(1..=5) {
+                variant = mutate(&variant, attractions, params, &mut rng);
+            }
+            variant
+        } else {
+            ga.random_chromosome(&mut rng)
+        };
+        population.push(ga.individual(ga.repair(chromosome)));
+    }
+
+    let mut convergence = Vec::new();
+    for generation in 0..=config.generations {
+        population.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let mean = population.iter().map(|p| p.1).sum::<f64>() / population.len() as f64;
+        convergence.push(ConvergencePoint {
+            iteration: generation as u32,
+            satisfaction: population[0].1,
+            temperature: 0.0,
+            mean_fitness: Some(mean),
+        });
+        if generation == config.generations || stop.should_stop() {
+            break;
+        }
+
+        let mut next: Vec<(Chromosome, f64)> = population[..ELITE_COUNT].to_vec();
+        while next.len() < population_size {
+            let first = tournament(&population, &mut rng);
+            let second = tournament(&population, &mut rng);
+
+            let mut child = if rng.gen_bool(crossover_rate) {
+                ordered_crossover(&population[first].0, &population[second].0, &mut rng)
+            } else {
+                population[first].0.clone()
+            };
+            if rng.gen_bool(mutation_rate) {
+                child = mutate(&child, attractions, params, &mut rng);
+            }
+            next.push(ga.individual(ga.repair(child)));
+        }
+        population = next;
+    }
+
+    let best = &population[0].0;
+    let mut itinerary = build_itinerary(best, attractions, params, travel);
+    itinerary.algorithm_used = "genetic".to_string();
+    itinerary.computation_ms = start.elapsed().as_millis();
+    itinerary.convergence_data = Some(convergence);
+    itinerary.compute_totals();
+    itinerary
+}
+
+/// Index of the fittest of `TOURNAMENT_SIZE` random individuals.
+fn tournament(population: &[(Chromosome, f64)], rng: &mut StdRng) -> usize {
+    (0..TOURNAMENT_SIZE)
+        .map(|_| rng.gen_range(0..population.len()))
+        .max_by(|&a, &b| population[a].1.total_cmp(&population[b].1))
+        .unwrap()
+}
+
+/// Order crossover on the days laid end to end: a slice of `first` keeps
+/// its place and order, the rest of `second`'s stops fill in around it, and
+/// the result is cut back into days of `first`'s lengths.
+fn ordered_crossover(first: &Chromosome, second: &Chromosome, rng: &mut StdRng) -> Chromosome {
+    let flat_first: Vec<u32> = first.iter().flatten().copied().collect();
+    if flat_first.is_empty() {
+        return second.clone();
+    }
+
+    let i = rng.gen_range(0..flat_first.len());
+    let j = rng.gen_range(i + 1..=flat_first.len());
+    let segment = &flat_first[i..j];
+    let kept: HashSet<u32> = segment.iter().copied().collect();
+    let rest: Vec<u32> = second.iter().flatten().copied().filter(|id| !kept.contains(id)).collect();
+
+    let split = i.min(rest.len());
+    let mut stops = rest[..split].iter().chain(segment).chain(&rest[split..]).copied();
+
+    let last = first.len() - 1;
+    first
+        .iter()
+        .enumerate()
+        .map(|(day, stops_first)| {
+            if day == last {
+                stops.by_ref().collect()
+            } else {
+                stops.by_ref().take(stops_first.len()).collect()
+            }
+        })
+        .collect()
+}
+
+struct Genetic<'a> {
+    attractions: &'a [Attraction],
+    params: &'a SolveParams,
+    travel: &'a dyn TravelTimeProvider,
+}
+
+impl Genetic<'_> {
+    fn individual(&self, chromosome: Chromosome) -> (Chromosome, f64) {
+        let fitness = evaluate_fitness(&chromosome, self.attractions, self.params, &Penalties::default(), self.travel);
+        (chromosome, fitness)
+    }
+
+    fn random_chromosome(&self, rng: &mut StdRng) -> Chromosome {
+        let mut ids: Vec<u32> = self.attractions.iter().map(|a| a.id).collect();
+        ids.shuffle(rng);
+        let days = self.params.num_days as usize;
+        let mut chromosome = vec![Vec::new(); days];
+        for (i, id) in ids.into_iter().enumerate() {
+            chromosome[i % days].push(id);
+        }
+        chromosome
+    }
+
+    /// Drop stops the schedule cannot keep, then try appending the most
+    /// preferred unvisited attractions to each day.
+    fn repair(&self, chromosome: Chromosome) -> Chromosome {
+        let decode = |c: &Chromosome| {
+            let mut kept = itinerary_to_chromosome(&build_itinerary(c, self.attractions, self.params, self.travel));
+            kept.resize(self.params.num_days as usize, Vec::new());
+            kept
+        };
+        let mut repaired = decode(&chromosome);
+
+        let visited: HashSet<u32> = repaired.iter().flatten().copied().collect();
+        let mut unvisited: Vec<&Attraction> =
+            self.attractions.iter().filter(|a| !visited.contains(&a.id)).collect();
+        unvisited.sort_by(|a, b| b.preference.total_cmp(&a.preference));
+
+        for attr in unvisited.into_iter().take(REPAIR_INSERTIONS) {
+            for day in 0..repaired.len() {
+                let mut trial = repaired.clone();
+                trial[day].push(attr.id);
+                let trial = decode(&trial);
+                if trial[day].contains(&attr.id) {
+                    repaired = trial;
+                    break;
+                }
+            }
+        }
+        repaired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::attraction::{Category, Location, TimeWindow};
+    use crate::utils::verifier::{verify_itinerary, verify_opening_hours};
+    use std::collections::HashMap;
+
+    fn create_test_attraction(id: u32, lat: f64, lng: f64, preference: f64) -> Attraction {
+        Attraction {
+            id,
+            name: format!("Attraction {}", id),
+            location: Location { lat, lng },
+            windows: vec![TimeWindow::new(540, 1020)],
+            duration: 60,
+            fee: 5.0,
+            preference,
+            category: Category::Museum,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
+        }
+    }
+
+    fn grid(n: u32) -> Vec<Attraction> {
+        (0..n)
+            .map(|i| {
+                let lat = 13.70 + 0.012 * (i % 5) as f64;
+                let lng = 100.46 + 0.012 * (i / 5) as f64;
+                create_test_attraction(i + 1, lat, lng, 0.3 + 0.05 * (i % 13) as f64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ga_is_valid_deterministic_and_reports_generations() {
+        let attractions = grid(20);
+        let params = SolveParams { daily_time_budget: 420, ..Default::default() };
+
+        let result = solve(&attractions, &params, 42);
+        assert!(verify_itinerary(&result, &params).is_ok());
+        assert!(verify_opening_hours(&result, &attractions, &params).is_ok());
+
+        let again = solve(&attractions, &params, 42);
+        assert_eq!(itinerary_to_chromosome(&result), itinerary_to_chromosome(&again));
+
+        let convergence = result.convergence_data.unwrap();
+        assert_eq!(convergence.len(), GENERATIONS + 1);
+        for point in &convergence {
+            assert!(point.mean_fitness.unwrap() <= point.satisfaction + 1e-9);
+        }
+        // Elitism: the best never gets worse
+        assert!(convergence.windows(2).all(|w| w[1].satisfaction >= w[0].satisfaction - 1e-9));
+    }
+
+    #[test]
+    fn test_ordered_crossover_keeps_ids_unique() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let first: Chromosome = vec![vec![1, 2, 3], vec![4, 5]];
+        let second: Chromosome = vec![vec![5, 6], vec![1, 7, 2]];
+
+        for _ in 0..50 {
+            let child = ordered_crossover(&first, &second, &mut rng);
+            assert_eq!(child.len(), 2);
+            let ids: Vec<u32> = child.iter().flatten().copied().collect();
+            let unique: HashSet<u32> = ids.iter().copied().collect();
+            assert_eq!(ids.len(), unique.len());
+        }
+    }
+}
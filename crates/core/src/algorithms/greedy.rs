@@ -1,41 +1,79 @@
 use crate::data_structures::{IntervalTree, PriorityQueue};
-use crate::models::attraction::{Attraction};
-use crate::models::itinerary::{DayPlan, Visit};
-use crate::models::graph::Graph;
+use crate::models::attraction::Attraction;
+use crate::models::itinerary::{AppointmentVisit, DayPlan, Visit};
 use crate::models::constraints::SolveParams;
 use crate::models::itinerary::Itinerary;
-use crate::utils::distance::haversine_distance;
+use crate::models::travel::{HaversineProvider, TravelTimeProvider};
+use crate::algorithms::solver::{Capabilities, NoConfig, Solver, StopToken};
 use std::collections::HashSet;
 use std::time::Instant;
 
-const CITY_SPEED_KMH: f64 = 30.0;
+pub struct GreedySolver;
 
-pub fn solve(attractions: &[Attraction], params: &SolveParams, _seed: u64) -> Itinerary {
-    let start = Instant::now();
-    
-    // Build interval tree for efficient time window queries
-    let mut interval_tree = IntervalTree::new();
-    for attr in attractions {
-        interval_tree.insert(crate::data_structures::interval_tree::Interval {
-            start: attr.open_time,
-            end: attr.close_time,
-            data: attr.id,
-        });
+impl Solver for GreedySolver {
+    type Config = NoConfig;
+
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+
+    fn label(&self) -> &'static str {
+        "Greedy (Baseline)"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
     }
 
-    // Build graph for travel time lookups
-    let graph = Graph::from_attractions(attractions);
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        _config: &NoConfig,
+        _seed: u64,
+        travel: &dyn TravelTimeProvider,
+        _stop: &StopToken,
+    ) -> Itinerary {
+        solve_with_travel(attractions, params, 0, travel)
+    }
+}
+
+pub fn solve(attractions: &[Attraction], params: &SolveParams, seed: u64) -> Itinerary {
+    solve_with_travel(attractions, params, seed, &HaversineProvider::default())
+}
+
+pub fn solve_with_travel(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    _seed: u64,
+    travel: &dyn TravelTimeProvider,
+) -> Itinerary {
+    let start = Instant::now();
     
     let mut visited = HashSet::new();
     let mut days = Vec::new();
     let mut total_cost = 0.0;
 
     for day in 1..=params.num_days {
+        // Build interval tree for efficient time window queries; hours
+        // depend on the weekday and holidays
+        let date = params.date_for_day(day);
+        let mut interval_tree = IntervalTree::new();
+        for attr in attractions {
+            for window in attr.hours_on(date, &params.holidays).windows() {
+                interval_tree.insert(crate::data_structures::interval_tree::Interval {
+                    start: window.open,
+                    end: window.close,
+                    data: attr.id,
+                });
+            }
+        }
+
         let day_plan = solve_day(
             day,
             attractions,
             &interval_tree,
-            &graph,
+            travel,
             params,
             &mut visited,
             &mut total_cost,
@@ -61,22 +99,29 @@ fn solve_day(
     day: u32,
     attractions: &[Attraction],
     interval_tree: &IntervalTree,
-    _graph: &Graph,
+    travel: &dyn TravelTimeProvider,
     params: &SolveParams,
     visited: &mut HashSet<u32>,
     total_cost: &mut f64,
 ) -> DayPlan {
     let mut visits = Vec::new();
-    let mut current_time = params.start_time;
-    let mut current_lat = params.hotel_lat;
-    let mut current_lng = params.hotel_lng;
+    let mut appointments = Vec::new();  // behind us
+    let mut current_time = params.day_start_time(day);
+    let mut current_location = params.day_start_location(day);
     let mut day_cost = 0.0;
+    let mut day_fare = 0.0;
     let mut day_satisfaction = 0.0;
     let mut travel_time_sum = 0.0;
 
+    let day_end = params.day_end(day);
+    let date = params.date_for_day(day);
+
     loop {
-        // Query attractions open at current time
-        let open_ids = interval_tree.query_open_at(current_time);
+        // Query attractions with an opening window left today, including
+        // ones that have not opened yet
+        let mut open_ids = interval_tree.query_range(current_time, day_end);
+        open_ids.sort_unstable();
+        open_ids.dedup();
         
         // Build priority queue of feasible attractions
         let mut pq = PriorityQueue::new();
@@ -86,94 +131,130 @@ fn solve_day(
                 continue;
             }
             
-            let attr = attractions.iter().find(|a| a.id == id).unwrap();
-            
-            // Calculate travel time from current position
-            let distance = haversine_distance(current_lat, current_lng, attr.location.lat, attr.location.lng);
-            let travel_time = ((distance / CITY_SPEED_KMH) * 60.0).ceil() as u32;
-            let arrival = current_time + travel_time;
-            let departure = arrival + attr.duration;
-            
-            // Check feasibility
-            if arrival < attr.open_time || departure > attr.close_time {
+            let Some(attr) = attractions.iter().find(|a| a.id == id) else {
                 continue;
-            }
+            };
+            let hours = attr.hours_on(date, &params.holidays);
             
             // Check budget
             if *total_cost + attr.fee > params.total_budget {
                 continue;
             }
             
-            // Check daily time budget (including return to hotel)
-            let return_distance = haversine_distance(attr.location.lat, attr.location.lng, params.hotel_lat, params.hotel_lng);
-            let return_time = ((return_distance / CITY_SPEED_KMH) * 60.0).ceil() as u32;
-            let total_time_if_visit = departure + return_time - params.start_time;
+            // Pick how to get there from current position
+            let max_fare = params.total_budget - *total_cost - attr.fee;
+            let leg = match params.transport.choose_leg(
+                &current_location,
+                &attr.location,
+                Some(current_time),
+                max_fare,
+                travel,
+            ) {
+                Some(leg) => leg,
+                None => continue,
+            };
+            let arrival = current_time + leg.travel_time;
+            
+            // Arriving early is fine: wait for the next window that fits
+            let visit_start = match hours.earliest_visit_start(arrival) {
+                Some(t) => t,
+                None => continue,
+            };
+            let departure = visit_start + attr.duration;
             
-            if total_time_if_visit > params.daily_time_budget {
+            // Check daily time budget (including the way to the next
+            // appointment or the day's end)
+            if !params.can_end_day(day, appointments.len(), &attr.location, departure, max_fare - leg.fare, travel) {
                 continue;
             }
             
-            // Score: preference / (travel_time + 1)
-            let score = attr.preference / (travel_time as f64 + 1.0);
-            pq.push(score, (id, travel_time));
+            // Score: preference / (travel_time + waiting + 1)
+            let idle = visit_start - current_time;
+            let score = attr.preference / (idle as f64 + 1.0);
+            pq.push(score, (attr, leg, arrival, visit_start));
         }
         
         // Pop best candidate
-        if let Some((best_id, travel_time)) = pq.pop() {
-            let attr = attractions.iter().find(|a| a.id == best_id).unwrap();
-            
-            let arrival = current_time + travel_time;
-            let departure = arrival + attr.duration;
+        if let Some((attr, leg, arrival, visit_start)) = pq.pop() {
+            let departure = visit_start + attr.duration;
             
             visits.push(Visit {
                 attraction_id: attr.id,
                 attraction_name: attr.name.clone(),
-                arrival_time: arrival,
+                arrival_time: visit_start,
                 departure_time: departure,
                 fee: attr.fee,
                 preference: attr.preference,
                 category: format!("{:?}", attr.category),
+                leg,
+                wait_minutes: visit_start - arrival,
             });
             
-            visited.insert(best_id);
-            *total_cost += attr.fee;
-            day_cost += attr.fee;
+            visited.insert(attr.id);
+            *total_cost += attr.fee + leg.fare;
+            day_cost += attr.fee + leg.fare;
+            day_fare += leg.fare;
             day_satisfaction += attr.preference;
-            travel_time_sum += travel_time as f64;
+            travel_time_sum += leg.travel_time as f64;
             
             current_time = departure;
-            current_lat = attr.location.lat;
-            current_lng = attr.location.lng;
+            current_location = attr.location;
+        } else if let Some((leg, free_at, location)) = params.attend_next(
+            day,
+            appointments.len(),
+            &current_location,
+            current_time,
+            params.total_budget - *total_cost,
+            travel,
+        ) {
+            // Nothing else fits before the next appointment
+            appointments.push(AppointmentVisit::new(&params.appointments(day)[appointments.len()], leg, current_time));
+            *total_cost += leg.fare;
+            day_cost += leg.fare;
+            day_fare += leg.fare;
+            travel_time_sum += leg.travel_time as f64;
+            current_time = free_at;
+            current_location = location;
         } else {
             break;
         }
     }
 
-    DayPlan {
+    let mut plan = DayPlan {
         day,
         visits,
+        appointments,
         total_travel_time: travel_time_sum as u32,
         total_cost: day_cost,
+        total_fare: day_fare,
         total_satisfaction: day_satisfaction,
-    }
+        return_leg: None,
+        return_arrival_time: None,
+    };
+    plan.close(params, &current_location, current_time, params.total_budget - *total_cost, travel);
+    *total_cost += plan.total_cost - day_cost;
+    plan
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::attraction::{Location, Category};
+    use crate::models::attraction::{Location, Category, TimeWindow};
+    use std::collections::HashMap;
 
     fn create_test_attraction(id: u32, lat: f64, lng: f64, preference: f64) -> Attraction {
         Attraction {
             id,
             name: format!("Attraction {}", id),
             location: Location { lat, lng },
-            open_time: 540,  // 9 AM
-            close_time: 1020, // 5 PM
+            windows: vec![TimeWindow::new(540, 1020)],  // 9 AM - 5 PM
             duration: 60,
             fee: 5.0,
             preference,
             category: Category::Museum,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
         }
     }
 
@@ -191,6 +272,7 @@ mod tests {
             start_time: 540,
             hotel_lat: 13.7563,
             hotel_lng: 100.5018,
+            ..Default::default()
         };
         
         let result = solve(&attractions, &params, 42);
@@ -212,6 +294,7 @@ mod tests {
             start_time: 540,
             hotel_lat: 13.7563,
             hotel_lng: 100.5018,
+            ..Default::default()
         };
         
         let result = solve(&attractions, &params, 42);
@@ -237,10 +320,57 @@ mod tests {
             start_time: 540,
             hotel_lat: 13.7563,
             hotel_lng: 100.5018,
+            ..Default::default()
         };
         
         let result = solve(&attractions, &params, 42);
         assert!(result.total_cost <= params.total_budget);
         assert!(result.total_attractions <= 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_greedy_uses_travel_provider() {
+        let attractions = vec![
+            create_test_attraction(1, 13.7563, 100.5018, 0.9),
+            create_test_attraction(2, 13.7600, 100.5050, 0.8),
+        ];
+        let params = SolveParams {
+            num_days: 1,
+            hotel_lat: 13.7000,
+            hotel_lng: 100.4500,
+            ..Default::default()
+        };
+
+        // A road network where attraction 2 is effectively unreachable
+        let points: Vec<Location> = attractions.iter().map(|a| a.location).collect();
+        let matrix = crate::models::travel::MatrixProvider::new(&points, vec![
+            vec![Some(0), Some(900)],
+            vec![Some(900), Some(0)],
+        ]).unwrap();
+
+        let result = solve_with_travel(&attractions, &params, 42, &matrix);
+        assert_eq!(result.total_attractions, 1);
+    }
+
+    #[test]
+    fn test_greedy_waits_for_evening_opening() {
+        let mut night_market = create_test_attraction(1, 13.7600, 100.5050, 0.9);
+        night_market.windows = vec![TimeWindow::new(1080, 1380)];  // 6 PM - 11 PM
+        let params = SolveParams {
+            num_days: 1,
+            daily_time_budget: 720,
+            start_time: 540,
+            hotel_lat: 13.7563,
+            hotel_lng: 100.5018,
+            ..Default::default()
+        };
+
+        let result = solve(&[night_market], &params, 42);
+        assert_eq!(result.total_attractions, 1);
+
+        let visit = &result.days[0].visits[0];
+        assert_eq!(visit.arrival_time, 1080);
+        assert_eq!(visit.wait_minutes, 1080 - 540 - visit.leg.travel_time);
+        assert!(crate::utils::verifier::verify_itinerary(&result, &params).is_ok());
+    }
+}
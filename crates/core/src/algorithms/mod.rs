@@ -1,5 +1,18 @@
+pub mod alns;
+pub mod branch_and_bound;
+pub mod genetic;
 pub mod greedy;
 pub mod simulated_annealing;
+pub mod solver;
 
 pub use greedy::solve as greedy_solve;
 pub use simulated_annealing::solve as sa_solve;
+pub use greedy::solve_with_travel as greedy_solve_with_travel;
+pub use simulated_annealing::solve_with_travel as sa_solve_with_travel;
+pub use simulated_annealing::solve_with_config as sa_solve_with_config;
+pub use simulated_annealing::solve_observed as sa_solve_observed;
+pub use simulated_annealing::{CoolingSchedule, SaConfig, SaEvent};
+pub use branch_and_bound::solve_with_travel as exact_solve_with_travel;
+pub use alns::solve_with_travel as alns_solve_with_travel;
+pub use genetic::solve_with_travel as ga_solve_with_travel;
+pub use solver::{Capabilities, DynSolver, Solver, SolverRegistry, StopToken};
@@ -1,41 +1,229 @@
 use crate::algorithms::greedy;
-use crate::models::attraction::{Attraction};
+use crate::models::attraction::{Attraction, Location};
 use crate::models::itinerary::{ConvergencePoint, DayPlan, Itinerary, Visit};
 use crate::models::constraints::SolveParams;
-use crate::utils::distance::haversine_distance;
+use crate::models::transport::Leg;
+use crate::models::travel::{HaversineProvider, TravelTimeProvider};
+use crate::algorithms::solver::{Capabilities, Solver, StopToken};
+use crate::utils::parallel::{default_threads, map_indexed};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-const CITY_SPEED_KMH: f64 = 30.0;
 const INITIAL_TEMP: f64 = 100.0;
 const COOLING_RATE: f64 = 0.995;
 const MIN_TEMP: f64 = 0.01;
 const MAX_ITERATIONS: usize = 10_000;
 
-type Chromosome = Vec<Vec<u32>>;
+pub(crate) type Chromosome = Vec<Vec<u32>>;
+
+/// How the temperature falls from `initial_temp` to `min_temp`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CoolingSchedule {
+    /// Multiply by `rate` every iteration.
+    Geometric { rate: f64 },
+    /// Fall by the same step every iteration, reaching `min_temp` at
+    /// `max_iterations`.
+    Linear,
+    /// Geometric, but after `reheat_after` iterations without a new best the
+    /// temperature is multiplied by `reheat_factor` (capped at
+    /// `initial_temp`).
+    Adaptive { rate: f64, reheat_after: usize, reheat_factor: f64 },
+}
+
+impl Default for CoolingSchedule {
+    fn default() -> Self {
+        CoolingSchedule::Geometric { rate: COOLING_RATE }
+    }
+}
+
+/// Fitness deducted for each stop the schedule cannot keep.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Penalties {
+    pub duplicate: f64,
+    pub no_transport: f64,
+    pub closed: f64,
+    /// Per dollar over the total budget.
+    pub over_budget: f64,
+    pub over_time: f64,
+}
+
+impl Default for Penalties {
+    fn default() -> Self {
+        Self { duplicate: 1.0, no_transport: 0.5, closed: 0.5, over_budget: 0.2, over_time: 0.3 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SaConfig {
+    pub initial_temp: f64,
+    pub min_temp: f64,
+    pub max_iterations: usize,
+    pub cooling: CoolingSchedule,
+    /// Stop after this much wall-clock time, keeping the best so far.
+    pub time_limit_ms: Option<u64>,
+    pub penalties: Penalties,
+    /// Independent chains run in parallel from different seeds, keeping the
+    /// best. One chain reproduces the single-start search.
+    pub chains: usize,
+}
+
+impl Default for SaConfig {
+    fn default() -> Self {
+        Self {
+            initial_temp: INITIAL_TEMP,
+            min_temp: MIN_TEMP,
+            max_iterations: MAX_ITERATIONS,
+            cooling: CoolingSchedule::default(),
+            time_limit_ms: None,
+            penalties: Penalties::default(),
+            chains: 1,
+        }
+    }
+}
+
+pub struct SaSolver;
+
+impl Solver for SaSolver {
+    type Config = SaConfig;
+
+    fn name(&self) -> &'static str {
+        "simulated_annealing"
+    }
+
+    fn label(&self) -> &'static str {
+        "Simulated Annealing"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities { stochastic: true, convergence_data: true, ..Default::default() }
+    }
+
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        config: &SaConfig,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+        stop: &StopToken,
+    ) -> Itinerary {
+        solve_with_config(attractions, params, config, seed, travel, stop)
+    }
+}
 
 pub fn solve(attractions: &[Attraction], params: &SolveParams, seed: u64) -> Itinerary {
+    solve_with_travel(attractions, params, seed, &HaversineProvider::default())
+}
+
+pub fn solve_with_travel(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+) -> Itinerary {
+    solve_with_config(attractions, params, &SaConfig::default(), seed, travel, &StopToken::new())
+}
+
+/// Live progress reported by `solve_observed`.
+#[derive(Debug, Clone)]
+pub enum SaEvent {
+    /// Sent every 50 iterations, as recorded in `convergence_data`.
+    Convergence(ConvergencePoint),
+    /// The best plan so far, sent with the first convergence point and then
+    /// whenever it improved.
+    Improved(Itinerary),
+}
+
+/// Like `solve_with_travel`, but with explicit settings; stops early with
+/// the best plan so far when `stop` fires.
+pub fn solve_with_config(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    config: &SaConfig,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+    stop: &StopToken,
+) -> Itinerary {
+    if config.chains > 1 {
+        return solve_multi_start(attractions, params, config, seed, travel, stop);
+    }
+    solve_observed(attractions, params, config, seed, travel, stop, &mut |_| {})
+}
+
+/// Run `config.chains` chains on separate threads and keep the best (the
+/// lowest chain on ties). Chain 0 uses `seed`, so the result only depends
+/// on the seed and the number of chains, never on the thread count.
+fn solve_multi_start(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    config: &SaConfig,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+    stop: &StopToken,
+) -> Itinerary {
     let start = Instant::now();
+    let chains = map_indexed(config.chains, config.chains.min(default_threads()), |chain| {
+        let chain_seed = seed.wrapping_add((chain as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        solve_observed(attractions, params, config, chain_seed, travel, stop, &mut |_| {})
+    });
+
+    let mut best = chains
+        .into_iter()
+        .reduce(|best, chain| if chain.total_satisfaction > best.total_satisfaction { chain } else { best })
+        .expect("at least two chains");
+    best.computation_ms = start.elapsed().as_millis();
+    best
+}
+
+/// Like `solve_with_config` with a single chain, passing convergence points
+/// and improved plans to `observe` while it runs.
+pub fn solve_observed(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    config: &SaConfig,
+    seed: u64,
+    travel: &dyn TravelTimeProvider,
+    stop: &StopToken,
+    observe: &mut dyn FnMut(SaEvent),
+) -> Itinerary {
+    let start = Instant::now();
+    let stop = match config.time_limit_ms {
+        Some(ms) => stop.with_time_limit(Duration::from_millis(ms)),
+        None => stop.clone(),
+    };
     let mut rng = StdRng::seed_from_u64(seed);
+    let penalties = &config.penalties;
     
     // Get greedy initial solution
-    let greedy_solution = greedy::solve(attractions, params, seed);
+    let greedy_solution = greedy::solve_with_travel(attractions, params, seed, travel);
     let mut best_chromosome = itinerary_to_chromosome(&greedy_solution);
-    let mut best_fitness = evaluate_fitness(&best_chromosome, attractions, params);
+    let mut best_fitness = evaluate_fitness(&best_chromosome, attractions, params, penalties, travel);
     
     let mut current = best_chromosome.clone();
     let mut current_fitness = best_fitness;
     
-    let mut temperature = INITIAL_TEMP;
+    let mut temperature = config.initial_temp;
+    let linear_step = (config.initial_temp - config.min_temp) / config.max_iterations.max(1) as f64;
+    let mut since_best = 0;
     let mut convergence = Vec::new();
     let mut iteration = 0;
+    // Report the starting plan too, so observers always have one to show
+    let mut improved = true;
     
-    while temperature > MIN_TEMP && iteration < MAX_ITERATIONS {
+    while temperature > config.min_temp && iteration < config.max_iterations {
+        if stop.should_stop() {
+            break;
+        }
+
         // Generate neighbor
         let neighbor = mutate(&current, attractions, params, &mut rng);
-        let neighbor_fitness = evaluate_fitness(&neighbor, attractions, params);
+        let neighbor_fitness = evaluate_fitness(&neighbor, attractions, params, penalties, travel);
         
         let delta = neighbor_fitness - current_fitness;
         
@@ -54,52 +242,88 @@ pub fn solve(attractions: &[Attraction], params: &SolveParams, seed: u64) -> Iti
             if current_fitness > best_fitness {
                 best_chromosome = current.clone();
                 best_fitness = current_fitness;
+                since_best = 0;
+                improved = true;
             }
         }
+        since_best += 1;
         
         // Record convergence every 50 iterations
         if iteration % 50 == 0 {
-            convergence.push(ConvergencePoint {
+            let point = ConvergencePoint {
                 iteration: iteration as u32,
                 satisfaction: best_fitness,
                 temperature,
-            });
+                mean_fitness: None,
+            };
+            observe(SaEvent::Convergence(point.clone()));
+            convergence.push(point);
+            if improved {
+                improved = false;
+                observe(SaEvent::Improved(to_itinerary(&best_chromosome, attractions, params, travel, start)));
+            }
         }
         
-        temperature *= COOLING_RATE;
+        temperature = match config.cooling {
+            CoolingSchedule::Geometric { rate } => temperature * rate,
+            CoolingSchedule::Linear => temperature - linear_step,
+            CoolingSchedule::Adaptive { rate, reheat_after, reheat_factor } => {
+                if since_best >= reheat_after.max(1) {
+                    since_best = 0;
+                    (temperature * reheat_factor).min(config.initial_temp)
+                } else {
+                    temperature * rate
+                }
+            }
+        };
         iteration += 1;
     }
     
-    // Convert best chromosome to itinerary
-    let mut itinerary = build_itinerary(&best_chromosome, attractions, params);
+    let mut itinerary = to_itinerary(&best_chromosome, attractions, params, travel, start);
+    itinerary.convergence_data = Some(convergence);
+    itinerary
+}
+
+fn to_itinerary(
+    chromosome: &Chromosome,
+    attractions: &[Attraction],
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+    start: Instant,
+) -> Itinerary {
+    let mut itinerary = build_itinerary(chromosome, attractions, params, travel);
     itinerary.algorithm_used = "simulated_annealing".to_string();
     itinerary.computation_ms = start.elapsed().as_millis();
-    itinerary.convergence_data = Some(convergence);
     itinerary.compute_totals();
-    
     itinerary
 }
 
-fn itinerary_to_chromosome(itinerary: &Itinerary) -> Chromosome {
+pub(crate) fn itinerary_to_chromosome(itinerary: &Itinerary) -> Chromosome {
     itinerary.days.iter()
         .map(|day| day.visits.iter().map(|v| v.attraction_id).collect())
         .collect()
 }
 
-fn evaluate_fitness(chromosome: &Chromosome, attractions: &[Attraction], params: &SolveParams) -> f64 {
+pub(crate) fn evaluate_fitness(
+    chromosome: &Chromosome,
+    attractions: &[Attraction],
+    params: &SolveParams,
+    penalties: &Penalties,
+    travel: &dyn TravelTimeProvider,
+) -> f64 {
     let mut fitness = 0.0;
     let mut total_cost = 0.0;
     let mut seen = HashSet::new();
     
-    for (_day_idx, day_attractions) in chromosome.iter().enumerate() {
-        let mut current_time = params.start_time;
-        let mut current_lat = params.hotel_lat;
-        let mut current_lng = params.hotel_lng;
+    for (day_idx, day_attractions) in chromosome.iter().enumerate() {
+        let day = day_idx as u32 + 1;
+        let date = params.date_for_day(day);
+        let mut position = DayPosition::start(params, day);
         
         for &attr_id in day_attractions {
             // Duplicate penalty
             if !seen.insert(attr_id) {
-                fitness -= 1.0;
+                fitness -= penalties.duplicate;
                 continue;
             }
             
@@ -107,45 +331,112 @@ fn evaluate_fitness(chromosome: &Chromosome, attractions: &[Attraction], params:
                 Some(a) => a,
                 None => continue,
             };
+            let hours = attr.hours_on(date, &params.holidays);
+            let money_left = params.total_budget - total_cost;
             
-            // Calculate travel time
-            let distance = haversine_distance(current_lat, current_lng, attr.location.lat, attr.location.lng);
-            let travel_time = ((distance / CITY_SPEED_KMH) * 60.0).ceil() as u32;
-            let arrival = current_time + travel_time;
-            let departure = arrival + attr.duration;
-            
-            // Time window check
-            if arrival < attr.open_time || departure > attr.close_time {
-                fitness -= 0.5;
-                continue;
-            }
-            
-            // Budget check
-            if total_cost + attr.fee > params.total_budget {
-                fitness -= 0.2 * (total_cost + attr.fee - params.total_budget);
-                continue;
-            }
+            // Fit the visit in, returning its cost and departure or the
+            // penalty for why it does not fit
+            let outcome = fit_around_appointments(params, day, position, money_left, travel, |at, fares| {
+                let money_left = money_left - fares;
+                // Pick a transport mode, preferring ones we can still afford
+                let max_fare = money_left - attr.fee;
+                let leg = params.transport
+                    .choose_leg(&at.location, &attr.location, Some(at.time), max_fare, travel)
+                    .or_else(|| params.transport.choose_leg(
+                        &at.location, &attr.location, Some(at.time), f64::INFINITY, travel,
+                    ))
+                    .ok_or(penalties.no_transport)?;
+                let arrival = at.time + leg.travel_time;
+                
+                // Time window check, waiting for the next window if early
+                let visit_start = hours.earliest_visit_start(arrival).ok_or(penalties.closed)?;
+                let departure = visit_start + attr.duration;
+                
+                // Budget check
+                let visit_cost = attr.fee + leg.fare;
+                if visit_cost > money_left {
+                    return Err(penalties.over_budget * (visit_cost - money_left));
+                }
+                
+                // Daily time budget check, including the way to the next
+                // appointment or the day's end
+                if !params.can_end_day(day, at.attended, &attr.location, departure, money_left - visit_cost, travel) {
+                    return Err(penalties.over_time);
+                }
+                Ok((visit_cost, departure))
+            });
             
-            // Daily time budget check
-            let day_end = departure - params.start_time;
-            if day_end > params.daily_time_budget {
-                fitness -= 0.3;
-                continue;
+            match outcome {
+                Ok(((visit_cost, departure), at, appointment_legs)) => {
+                    // Valid visit - add preference
+                    fitness += attr.preference;
+                    total_cost += visit_cost + appointment_legs.iter().map(|(leg, _)| leg.fare).sum::<f64>();
+                    position = DayPosition { time: departure, location: attr.location, attended: at.attended };
+                }
+                Err(penalty) => fitness -= penalty,
             }
-            
-            // Valid visit - add preference
-            fitness += attr.preference;
-            total_cost += attr.fee;
-            current_time = departure;
-            current_lat = attr.location.lat;
-            current_lng = attr.location.lng;
+        }
+        
+        // The rest of the day's legs are paid for like any other
+        let money_left = params.total_budget - total_cost;
+        if let Some((fares, _)) =
+            params.finish_day(day, position.attended, &position.location, position.time, money_left, travel)
+        {
+            total_cost += fares;
         }
     }
     
     fitness
 }
 
-fn mutate(chromosome: &Chromosome, attractions: &[Attraction], params: &SolveParams, rng: &mut StdRng) -> Chromosome {
+/// Where a partial day leaves the traveller.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DayPosition {
+    pub time: u32,
+    pub location: Location,
+    /// Appointments already behind them.
+    pub attended: usize,
+}
+
+impl DayPosition {
+    pub fn start(params: &SolveParams, day: u32) -> Self {
+        Self { time: params.day_start_time(day), location: params.day_start_location(day), attended: 0 }
+    }
+}
+
+/// Legs taken to appointments, each with the time it left.
+pub(crate) type AppointmentLegs = Vec<(Leg, u32)>;
+
+/// Try `visit` from `position`; if it does not fit, attend the next
+/// appointment and try again from there, for as long as appointments are
+/// left. `visit` also gets the fares spent on the way. Returns the first
+/// fit with the position it was made from and the legs taken to
+/// appointments on the way, or the last failure.
+pub(crate) fn fit_around_appointments<T, E>(
+    params: &SolveParams,
+    day: u32,
+    mut position: DayPosition,
+    max_fare: f64,
+    travel: &dyn TravelTimeProvider,
+    mut visit: impl FnMut(DayPosition, f64) -> Result<T, E>,
+) -> Result<(T, DayPosition, AppointmentLegs), E> {
+    let mut legs = Vec::new();
+    let mut fares = 0.0;
+    loop {
+        let failure = match visit(position, fares) {
+            Ok(fit) => return Ok((fit, position, legs)),
+            Err(e) => e,
+        };
+        let (leg, time, location) = params
+            .attend_next(day, position.attended, &position.location, position.time, max_fare - fares, travel)
+            .ok_or(failure)?;
+        legs.push((leg, position.time));
+        fares += leg.fare;
+        position = DayPosition { time, location, attended: position.attended + 1 };
+    }
+}
+
+pub(crate) fn mutate(chromosome: &Chromosome, attractions: &[Attraction], params: &SolveParams, rng: &mut StdRng) -> Chromosome {
     let mut new = chromosome.clone();
     let mutation_type = rng.gen_range(0..3);
     
@@ -203,18 +494,30 @@ fn insert_unvisited(chromosome: &mut Chromosome, attractions: &[Attraction], _pa
     }
 }
 
-fn build_itinerary(chromosome: &Chromosome, attractions: &[Attraction], params: &SolveParams) -> Itinerary {
+pub(crate) fn build_itinerary(
+    chromosome: &Chromosome,
+    attractions: &[Attraction],
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+) -> Itinerary {
     let mut days = Vec::new();
     let mut total_cost = 0.0;
     
     for (day_num, day_attrs) in chromosome.iter().enumerate() {
-        let mut visits = Vec::new();
-        let mut current_time = params.start_time;
-        let mut current_lat = params.hotel_lat;
-        let mut current_lng = params.hotel_lng;
-        let mut day_cost = 0.0;
-        let mut day_satisfaction = 0.0;
-        let mut travel_time_sum = 0;
+        let day = day_num as u32 + 1;
+        let date = params.date_for_day(day);
+        let mut plan = DayPlan {
+            day,
+            visits: Vec::new(),
+            appointments: Vec::new(),
+            total_travel_time: 0,
+            total_cost: 0.0,
+            total_fare: 0.0,
+            total_satisfaction: 0.0,
+            return_leg: None,
+            return_arrival_time: None,
+        };
+        let mut position = DayPosition::start(params, day);
         
         for &attr_id in day_attrs {
             let attr = match attractions.iter().find(|a| a.id == attr_id) {
@@ -222,48 +525,58 @@ fn build_itinerary(chromosome: &Chromosome, attractions: &[Attraction], params:
                 None => continue,
             };
             
-            let distance = haversine_distance(current_lat, current_lng, attr.location.lat, attr.location.lng);
-            let travel_time = ((distance / CITY_SPEED_KMH) * 60.0).ceil() as u32;
-            let arrival = current_time + travel_time;
-            let departure = arrival + attr.duration;
-            
             // Hard constraint checks
-            if arrival < attr.open_time || departure > attr.close_time {
-                continue;
-            }
             if total_cost + attr.fee > params.total_budget {
                 continue;
             }
-            if departure - params.start_time > params.daily_time_budget {
+            let max_fare = params.total_budget - total_cost - attr.fee;
+            let hours = attr.hours_on(date, &params.holidays);
+            let fit = fit_around_appointments(params, day, position, max_fare, travel, |at, fares| {
+                let max_fare = max_fare - fares;
+                let leg = params.transport
+                    .choose_leg(&at.location, &attr.location, Some(at.time), max_fare, travel)
+                    .ok_or(())?;
+                let arrival = at.time + leg.travel_time;
+                let visit_start = hours.earliest_visit_start(arrival).ok_or(())?;
+                let departure = visit_start + attr.duration;
+                if !params.can_end_day(day, at.attended, &attr.location, departure, max_fare - leg.fare, travel) {
+                    return Err(());
+                }
+                Ok((leg, arrival, visit_start))
+            });
+            let Ok(((leg, arrival, visit_start), at, appointment_legs)) = fit else {
                 continue;
-            }
+            };
+            let departure = visit_start + attr.duration;
             
-            visits.push(Visit {
+            for (appointment_leg, left_at) in appointment_legs {
+                plan.attend(params, appointment_leg, left_at);
+                total_cost += appointment_leg.fare;
+            }
+            plan.visits.push(Visit {
                 attraction_id: attr.id,
                 attraction_name: attr.name.clone(),
-                arrival_time: arrival,
+                arrival_time: visit_start,
                 departure_time: departure,
                 fee: attr.fee,
                 preference: attr.preference,
                 category: format!("{:?}", attr.category),
+                leg,
+                wait_minutes: visit_start - arrival,
             });
             
-            total_cost += attr.fee;
-            day_cost += attr.fee;
-            day_satisfaction += attr.preference;
-            travel_time_sum += travel_time;
-            current_time = departure;
-            current_lat = attr.location.lat;
-            current_lng = attr.location.lng;
+            total_cost += attr.fee + leg.fare;
+            plan.total_cost += attr.fee + leg.fare;
+            plan.total_fare += leg.fare;
+            plan.total_satisfaction += attr.preference;
+            plan.total_travel_time += leg.travel_time;
+            position = DayPosition { time: departure, location: attr.location, attended: at.attended };
         }
         
-        days.push(DayPlan {
-            day: (day_num + 1) as u32,
-            visits,
-            total_travel_time: travel_time_sum,
-            total_cost: day_cost,
-            total_satisfaction: day_satisfaction,
-        });
+        let before_close = plan.total_cost;
+        plan.close(params, &position.location, position.time, params.total_budget - total_cost, travel);
+        total_cost += plan.total_cost - before_close;
+        days.push(plan);
     }
     
     Itinerary {
@@ -280,7 +593,8 @@ fn build_itinerary(chromosome: &Chromosome, attractions: &[Attraction], params:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::attraction::{Location, Category};
+    use crate::models::attraction::{Location, Category, TimeWindow};
+    use std::collections::HashMap;
     use crate::utils::verifier::verify_itinerary;
 
     fn create_test_attraction(id: u32, lat: f64, lng: f64, preference: f64) -> Attraction {
@@ -288,12 +602,14 @@ mod tests {
             id,
             name: format!("Attraction {}", id),
             location: Location { lat, lng },
-            open_time: 540,
-            close_time: 1020,
+            windows: vec![TimeWindow::new(540, 1020)],
             duration: 60,
             fee: 5.0,
             preference,
             category: Category::Museum,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
         }
     }
 
@@ -343,4 +659,109 @@ mod tests {
         let convergence = result.convergence_data.unwrap();
         assert!(!convergence.is_empty());
     }
+
+    #[test]
+    fn test_sa_cooling_schedules() {
+        let attractions: Vec<Attraction> = (0..8)
+            .map(|i| create_test_attraction(i + 1, 13.75 + 0.005 * i as f64, 100.50, 0.5 + 0.05 * i as f64))
+            .collect();
+        let params = SolveParams::default();
+        let travel = HaversineProvider::default();
+
+        // Defaults reproduce the plain entry point
+        let default = solve_with_config(&attractions, &params, &SaConfig::default(), 42, &travel, &StopToken::new());
+        let plain = solve(&attractions, &params, 42);
+        assert_eq!(itinerary_to_chromosome(&default), itinerary_to_chromosome(&plain));
+
+        let linear = SaConfig { cooling: CoolingSchedule::Linear, max_iterations: 500, ..Default::default() };
+        let result = solve_with_config(&attractions, &params, &linear, 42, &travel, &StopToken::new());
+        assert!(verify_itinerary(&result, &params).is_ok());
+        let temps: Vec<f64> = result.convergence_data.unwrap().iter().map(|p| p.temperature).collect();
+        assert_eq!(temps.len(), 10);
+        assert!((temps[0] - temps[1] - 50.0 * (INITIAL_TEMP - MIN_TEMP) / 500.0).abs() < 1e-6);
+
+        let adaptive = SaConfig {
+            cooling: CoolingSchedule::Adaptive { rate: 0.99, reheat_after: 100, reheat_factor: 50.0 },
+            max_iterations: 2_000,
+            ..Default::default()
+        };
+        let result = solve_with_config(&attractions, &params, &adaptive, 42, &travel, &StopToken::new());
+        assert!(verify_itinerary(&result, &params).is_ok());
+        let temps: Vec<f64> = result.convergence_data.unwrap().iter().map(|p| p.temperature).collect();
+        assert!(temps.windows(2).any(|w| w[1] > w[0]), "adaptive schedule never reheated");
+    }
+
+    #[test]
+    fn test_sa_time_limit_and_partial_json_config() {
+        let attractions = vec![create_test_attraction(1, 13.7563, 100.5018, 0.9)];
+        let params = SolveParams::default();
+
+        let config: SaConfig = serde_json::from_str(
+            r#"{ "time_limit_ms": 0, "cooling": { "type": "linear" }, "penalties": { "over_time": 1.0 } }"#,
+        )
+        .unwrap();
+        assert_eq!(config.max_iterations, MAX_ITERATIONS);
+        assert_eq!(config.penalties.duplicate, Penalties::default().duplicate);
+        assert_eq!(config.penalties.over_time, 1.0);
+
+        let result = solve_with_config(&attractions, &params, &config, 42, &HaversineProvider::default(), &StopToken::new());
+        assert!(verify_itinerary(&result, &params).is_ok());
+        assert!(result.convergence_data.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sa_observer_sees_convergence_and_improvements() {
+        let attractions: Vec<Attraction> = (0..8)
+            .map(|i| create_test_attraction(i + 1, 13.75 + 0.005 * i as f64, 100.50, 0.3 + 0.08 * i as f64))
+            .collect();
+        let params = SolveParams::default();
+        let travel = HaversineProvider::default();
+
+        let mut points = Vec::new();
+        let mut improved = Vec::new();
+        let result = solve_observed(
+            &attractions,
+            &params,
+            &SaConfig::default(),
+            7,
+            &travel,
+            &StopToken::new(),
+            &mut |event| match event {
+                SaEvent::Convergence(point) => points.push(point.iteration),
+                SaEvent::Improved(itinerary) => improved.push(itinerary),
+            },
+        );
+
+        let recorded: Vec<u32> = result.convergence_data.unwrap().iter().map(|p| p.iteration).collect();
+        assert_eq!(points, recorded);
+        for itinerary in &improved {
+            assert!(verify_itinerary(itinerary, &params).is_ok());
+            assert_eq!(itinerary.algorithm_used, "simulated_annealing");
+        }
+        assert_eq!(improved.last().unwrap().total_satisfaction, result.total_satisfaction);
+
+        // Observing doesn't change the search
+        let plain = solve_with_config(&attractions, &params, &SaConfig::default(), 7, &travel, &StopToken::new());
+        assert_eq!(plain.total_satisfaction, result.total_satisfaction);
+    }
+
+    #[test]
+    fn test_multi_start_keeps_the_best_chain() {
+        let attractions: Vec<Attraction> = (0..8)
+            .map(|i| create_test_attraction(i + 1, 13.75 + 0.005 * i as f64, 100.50, 0.3 + 0.08 * i as f64))
+            .collect();
+        let params = SolveParams::default();
+        let travel = HaversineProvider::default();
+        let config = SaConfig { initial_temp: 1.0, max_iterations: 2_000, ..SaConfig::default() };
+
+        let single = solve_with_config(&attractions, &params, &config, 11, &travel, &StopToken::new());
+        let multi_config = SaConfig { chains: 4, ..config };
+        let multi = solve_with_config(&attractions, &params, &multi_config, 11, &travel, &StopToken::new());
+        let again = solve_with_config(&attractions, &params, &multi_config, 11, &travel, &StopToken::new());
+
+        assert!(verify_itinerary(&multi, &params).is_ok());
+        assert!(multi.total_satisfaction >= single.total_satisfaction);
+        assert_eq!(multi.total_satisfaction, again.total_satisfaction);
+        assert!(multi.convergence_data.is_some());
+    }
 }
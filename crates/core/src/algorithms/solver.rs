@@ -0,0 +1,380 @@
+//! Common interface over the solvers so callers can enumerate and run them
+//! by name instead of matching on strings.
+
+use crate::algorithms::alns::AlnsSolver;
+use crate::algorithms::branch_and_bound::ExactSolver;
+use crate::algorithms::genetic::GeneticSolver;
+use crate::algorithms::greedy::GreedySolver;
+use crate::algorithms::simulated_annealing::SaSolver;
+use crate::error::Error;
+use crate::models::attraction::Attraction;
+use crate::models::constraints::SolveParams;
+use crate::models::itinerary::Itinerary;
+use crate::models::travel::TravelTimeProvider;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// What callers can expect from a solver.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Capabilities {
+    /// Results depend on the seed, so repeated runs are worth averaging.
+    pub stochastic: bool,
+    /// Proves optimality (or bounds the optimum) when it finishes.
+    pub exact: bool,
+    /// Fills in `Itinerary::convergence_data`.
+    pub convergence_data: bool,
+    /// Largest dataset the solver is meant to run on.
+    pub max_attractions: Option<usize>,
+}
+
+impl Capabilities {
+    pub fn supports(&self, n_attractions: usize) -> bool {
+        self.max_attractions.is_none_or(|max| n_attractions <= max)
+    }
+}
+
+/// Tells a running solver to stop and return its best itinerary so far,
+/// either at a deadline or when cancelled. Clones share the cancel flag.
+#[derive(Debug, Clone, Default)]
+pub struct StopToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl StopToken {
+    /// A token that only stops when cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same cancel flag, also stopping `limit` from now (or at the current
+    /// deadline if that is sooner).
+    pub fn with_time_limit(&self, limit: Duration) -> Self {
+        let deadline = Instant::now() + limit;
+        Self {
+            cancelled: self.cancelled.clone(),
+            deadline: Some(self.deadline.map_or(deadline, |d| d.min(deadline))),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn should_stop(&self) -> bool {
+        self.is_cancelled() || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Configuration for solvers without tunable settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoConfig {}
+
+pub trait Solver: Send + Sync {
+    type Config: Default + Clone + Serialize + DeserializeOwned + Send + Sync;
+
+    /// Identifier used in requests and in `Itinerary::algorithm_used`.
+    fn name(&self) -> &'static str;
+    fn label(&self) -> &'static str;
+    fn capabilities(&self) -> Capabilities;
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        config: &Self::Config,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+        stop: &StopToken,
+    ) -> Itinerary;
+}
+
+/// Object-safe view of a `Solver`, with the configuration as JSON.
+pub trait DynSolver: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn label(&self) -> &'static str;
+    fn capabilities(&self) -> Capabilities;
+    fn default_config(&self) -> Value;
+    /// `config` layered over the defaults, with every setting filled in.
+    fn resolve_config(&self, config: Option<&Value>) -> Result<Value, Error>;
+    /// Solve with `config` layered over the defaults; fails on unknown or
+    /// mistyped settings, unusable parameters and datasets the solver does
+    /// not handle.
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        config: Option<&Value>,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+        stop: &StopToken,
+    ) -> Result<Itinerary, Error>;
+    /// Solve with the default configuration.
+    fn solve_default(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+    ) -> Itinerary;
+}
+
+impl<S: Solver> DynSolver for S {
+    fn name(&self) -> &'static str {
+        Solver::name(self)
+    }
+
+    fn label(&self) -> &'static str {
+        Solver::label(self)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Solver::capabilities(self)
+    }
+
+    fn default_config(&self) -> Value {
+        serde_json::to_value(S::Config::default()).unwrap_or(Value::Null)
+    }
+
+    fn resolve_config(&self, config: Option<&Value>) -> Result<Value, Error> {
+        let config = parse_config::<S>(self, config)?;
+        serde_json::to_value(config).map_err(|e| Error::InvalidConfig {
+            solver: Solver::name(self).to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    fn solve(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        config: Option<&Value>,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+        stop: &StopToken,
+    ) -> Result<Itinerary, Error> {
+        let config = parse_config::<S>(self, config)?;
+        check_instance(self, attractions, params)?;
+        Ok(Solver::solve(self, attractions, params, &config, seed, travel, stop))
+    }
+
+    fn solve_default(
+        &self,
+        attractions: &[Attraction],
+        params: &SolveParams,
+        seed: u64,
+        travel: &dyn TravelTimeProvider,
+    ) -> Itinerary {
+        Solver::solve(self, attractions, params, &S::Config::default(), seed, travel, &StopToken::new())
+    }
+}
+
+fn parse_config<S: Solver>(solver: &S, config: Option<&Value>) -> Result<S::Config, Error> {
+    match config {
+        Some(value) if !value.is_null() => {
+            serde_json::from_value(value.clone()).map_err(|e| Error::InvalidConfig {
+                solver: Solver::name(solver).to_string(),
+                message: e.to_string(),
+            })
+        }
+        _ => Ok(S::Config::default()),
+    }
+}
+
+/// Reject instances `solver` cannot plan: parameters that fail
+/// `SolveParams::validate`, or more attractions than it handles.
+/// `DynSolver::solve` runs this first; callers can use it to fail before
+/// queueing work.
+pub fn check_instance(solver: &dyn DynSolver, attractions: &[Attraction], params: &SolveParams) -> Result<(), Error> {
+    params.validate().map_err(Error::InvalidParams)?;
+    let capabilities = solver.capabilities();
+    if !capabilities.supports(attractions.len()) {
+        return Err(Error::Infeasible(format!(
+            "{} handles at most {} attractions, the dataset has {}",
+            solver.name(),
+            capabilities.max_attractions.unwrap_or_default(),
+            attractions.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Solvers available by name, in registration order.
+#[derive(Clone)]
+pub struct SolverRegistry {
+    solvers: Vec<Arc<dyn DynSolver>>,
+}
+
+impl SolverRegistry {
+    pub fn empty() -> Self {
+        Self { solvers: Vec::new() }
+    }
+
+    /// Add a solver, replacing any registered under the same name.
+    pub fn register<S: Solver + 'static>(&mut self, solver: S) {
+        let name = Solver::name(&solver);
+        self.solvers.retain(|s| s.name() != name);
+        self.solvers.push(Arc::new(solver));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn DynSolver>> {
+        self.solvers.iter().find(|s| s.name() == name).cloned()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn DynSolver>> {
+        self.solvers.iter()
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.solvers.iter().map(|s| s.name()).collect()
+    }
+}
+
+impl Default for SolverRegistry {
+    /// Every solver in this crate, baseline first.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(GreedySolver);
+        registry.register(SaSolver);
+        registry.register(GeneticSolver);
+        registry.register(AlnsSolver);
+        registry.register(ExactSolver);
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::attraction::{Category, Location, TimeWindow};
+    use crate::models::travel::HaversineProvider;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn attractions() -> Vec<Attraction> {
+        (0..6)
+            .map(|i| Attraction {
+                id: i + 1,
+                name: format!("Attraction {}", i + 1),
+                location: Location { lat: 13.75 + 0.01 * i as f64, lng: 100.50 },
+                windows: vec![TimeWindow::new(540, 1020)],
+                duration: 60,
+                fee: 5.0,
+                preference: 0.5,
+                category: Category::Museum,
+                weekday_windows: HashMap::new(),
+                holiday_windows: None,
+                closed_dates: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_registry_names_match_algorithm_used() {
+        let registry = SolverRegistry::default();
+        assert_eq!(
+            registry.names(),
+            vec!["greedy", "simulated_annealing", "genetic", "alns", "branch_and_bound"]
+        );
+
+        let attractions = attractions();
+        let params = SolveParams::default();
+        let travel = HaversineProvider::default();
+        for solver in registry.iter() {
+            let config = solver.default_config();
+            let itinerary = solver.solve(&attractions, &params, Some(&config), 7, &travel, &StopToken::new()).unwrap();
+            assert_eq!(itinerary.algorithm_used, solver.name());
+        }
+        assert!(registry.get("nope").is_none());
+    }
+
+    #[test]
+    fn test_bad_config_is_rejected() {
+        let registry = SolverRegistry::default();
+        let genetic = registry.get("genetic").unwrap();
+        let travel = HaversineProvider::default();
+        let params = SolveParams::default();
+
+        let err = genetic
+            .solve(&attractions(), &params, Some(&json!({ "generations": "many" })), 1, &travel, &StopToken::new())
+            .unwrap_err();
+        assert!(matches!(&err, Error::InvalidConfig { solver, .. } if solver == "genetic"));
+        assert!(genetic
+            .solve(&attractions(), &params, Some(&json!({ "populaton_size": 10 })), 1, &travel, &StopToken::new())
+            .is_err());
+
+        // Partial configs keep the other defaults
+        let resolved = genetic.resolve_config(Some(&json!({ "generations": 3 }))).unwrap();
+        assert_eq!(resolved["generations"], 3);
+        assert_eq!(resolved["population_size"], genetic.default_config()["population_size"]);
+        assert_eq!(genetic.resolve_config(None).unwrap(), genetic.default_config());
+        let itinerary = genetic
+            .solve(&attractions(), &params, Some(&json!({ "generations": 3 })), 1, &travel, &StopToken::new())
+            .unwrap();
+        assert_eq!(itinerary.convergence_data.unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_unusable_instances_are_rejected() {
+        let registry = SolverRegistry::default();
+        let travel = HaversineProvider::default();
+        let greedy = registry.get("greedy").unwrap();
+
+        let no_days = SolveParams { num_days: 0, ..Default::default() };
+        let err = greedy.solve(&attractions(), &no_days, None, 1, &travel, &StopToken::new()).unwrap_err();
+        assert!(matches!(err, Error::InvalidParams(_)));
+
+        let many: Vec<Attraction> = (0..40)
+            .map(|i| Attraction { id: i + 1, ..attractions()[0].clone() })
+            .collect();
+        let exact = registry.get("branch_and_bound").unwrap();
+        let err = exact
+            .solve(&many, &SolveParams::default(), None, 1, &travel, &StopToken::new())
+            .unwrap_err();
+        assert!(matches!(err, Error::Infeasible(_)));
+        assert!(greedy.solve(&many, &SolveParams::default(), None, 1, &travel, &StopToken::new()).is_ok());
+    }
+
+    #[test]
+    fn test_solvers_stop_early_with_a_valid_plan() {
+        use crate::algorithms::branch_and_bound::solve_exact;
+        use crate::utils::verifier::verify_itinerary;
+
+        let attractions = attractions();
+        let params = SolveParams::default();
+        let travel = HaversineProvider::default();
+        let cancelled = StopToken::new();
+        cancelled.cancel();
+
+        for solver in SolverRegistry::default().iter() {
+            let itinerary = solver.solve(&attractions, &params, None, 3, &travel, &cancelled).unwrap();
+            assert!(verify_itinerary(&itinerary, &params).is_ok(), "{}", solver.name());
+            assert!(itinerary.total_attractions > 0, "{}", solver.name());
+        }
+
+        // A long SA run ends at the deadline
+        let sa = SolverRegistry::default().get("simulated_annealing").unwrap();
+        let config = json!({ "max_iterations": 100_000_000, "min_temp": 1e-300 });
+        let expired = StopToken::new().with_time_limit(Duration::from_millis(50));
+        let start = Instant::now();
+        let itinerary = sa.solve(&attractions, &params, Some(&config), 3, &travel, &expired).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(verify_itinerary(&itinerary, &params).is_ok());
+
+        let exact = solve_exact(&attractions, &params, &travel, u64::MAX, &cancelled);
+        assert_eq!(exact.nodes, 1);
+        assert!(exact.upper_bound >= exact.itinerary.total_satisfaction);
+    }
+}
@@ -1,11 +1,12 @@
 use std::cmp::max;
 
 /// Interval Tree for time-window queries.
-/// Stores attractions by their [open_time, close_time] interval.
+/// Stores one [open, close] interval per attraction opening window, so an
+/// attraction with a lunch closure appears twice.
 /// Query: "which attractions are open at time T?" in O(log n + k)
 ///
 /// Implementation: augmented BST where each node stores the
-/// maximum close_time in its subtree for efficient pruning.
+/// maximum close time in its subtree for efficient pruning.
 
 #[derive(Debug, Clone)]
 pub struct Interval {
@@ -92,8 +93,76 @@ impl IntervalTree {
     }
 }
 
+impl IntervalTree {
+    /// Data of every interval overlapping `[start, end)`, e.g. attractions
+    /// with some window left between now and the end of the day.
+    pub fn query_range(&self, start: u32, end: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        Self::query_range_recursive(&self.root, start, end, &mut result);
+        result
+    }
+
+    fn query_range_recursive(node: &Option<Box<Node>>, start: u32, end: u32, result: &mut Vec<u32>) {
+        if let Some(n) = node {
+            if n.max_end <= start {
+                return;
+            }
+
+            Self::query_range_recursive(&n.left, start, end, result);
+
+            if n.interval.start < end && start < n.interval.end {
+                result.push(n.interval.data);
+            }
+
+            // Right subtree starts no earlier than this node
+            if n.interval.start < end {
+                Self::query_range_recursive(&n.right, start, end, result);
+            }
+        }
+    }
+}
+
 impl Default for IntervalTree {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> IntervalTree {
+        let mut tree = IntervalTree::new();
+        tree.insert(Interval { start: 480, end: 720, data: 1 });
+        tree.insert(Interval { start: 780, end: 1020, data: 1 });
+        tree.insert(Interval { start: 600, end: 1320, data: 2 });
+        tree.insert(Interval { start: 1080, end: 1380, data: 3 });
+        tree
+    }
+
+    #[test]
+    fn test_query_open_at_respects_gaps() {
+        let tree = tree();
+        let mut at_lunch = tree.query_open_at(750);
+        at_lunch.sort();
+        assert_eq!(at_lunch, vec![2]);
+
+        let mut morning = tree.query_open_at(600);
+        morning.sort();
+        assert_eq!(morning, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_query_range() {
+        let tree = tree();
+        let mut ids = tree.query_range(730, 790);
+        ids.sort();
+        assert_eq!(ids, vec![1, 2]);
+
+        let mut evening = tree.query_range(1300, 1400);
+        evening.sort();
+        assert_eq!(evening, vec![2, 3]);
+
+        assert!(tree.query_range(1380, 1440).is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+//! Errors returned by the crate's fallible entry points: solving through a
+//! `DynSolver`, verifying itineraries and loading data files.
+
+use crate::models::constraints::ParamError;
+use crate::utils::verifier::Violation;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Solve parameters no plan can be built from, every problem listed.
+    InvalidParams(Vec<ParamError>),
+    /// Solver settings that do not match the solver's configuration type.
+    InvalidConfig { solver: String, message: String },
+    /// An itinerary names an attraction the dataset does not have.
+    UnknownAttraction { id: u32, day: Option<u32> },
+    /// The instance cannot be solved as posed, e.g. it is too large for
+    /// the chosen solver.
+    Infeasible(String),
+    /// An itinerary breaks one of the verifier's rules.
+    InvalidItinerary(Violation),
+    /// A dataset, travel matrix, traffic profile or holiday calendar that
+    /// could not be read or parsed. `line` and `column` point into the file
+    /// when the problem is a syntax or type error.
+    Dataset {
+        path: String,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+}
+
+impl Error {
+    pub(crate) fn read(path: &Path, e: std::io::Error) -> Self {
+        Self::dataset(path, format!("Failed to read: {}", e))
+    }
+
+    pub(crate) fn parse(path: &Path, e: serde_json::Error) -> Self {
+        Error::Dataset {
+            path: path.display().to_string(),
+            line: Some(e.line()),
+            column: Some(e.column()),
+            message: e.to_string(),
+        }
+    }
+
+    pub(crate) fn dataset(path: &Path, message: impl Into<String>) -> Self {
+        Error::Dataset {
+            path: path.display().to_string(),
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidParams(problems) => {
+                let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+                write!(f, "Invalid parameters: {}", problems.join("; "))
+            }
+            Error::InvalidConfig { solver, message } => write!(f, "Invalid {} config: {}", solver, message),
+            Error::UnknownAttraction { id, day: Some(day) } => {
+                write!(f, "Unknown attraction {} on day {}", id, day)
+            }
+            Error::UnknownAttraction { id, day: None } => write!(f, "Unknown attraction {}", id),
+            Error::Infeasible(message) => write!(f, "Infeasible instance: {}", message),
+            Error::InvalidItinerary(violation) => write!(f, "{}", violation),
+            Error::Dataset { path, line: Some(line), column: Some(column), message } => {
+                write!(f, "{}:{}:{}: {}", path, line, column, message)
+            }
+            Error::Dataset { path, message, .. } => write!(f, "{}: {}", path, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_keeps_location() {
+        let json_err = serde_json::from_str::<Vec<u32>>("[1,\n 2,\n \"three\"]").unwrap_err();
+        let err = Error::parse(Path::new("data/datasets/small.json"), json_err);
+
+        match &err {
+            Error::Dataset { line, column, .. } => {
+                assert_eq!(*line, Some(3));
+                assert!(column.is_some());
+            }
+            other => panic!("unexpected error {:?}", other),
+        }
+        assert!(err.to_string().starts_with("data/datasets/small.json:3:"));
+    }
+}
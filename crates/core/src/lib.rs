@@ -2,13 +2,23 @@ pub mod models;
 pub mod algorithms;
 pub mod data_structures;
 pub mod utils;
+pub mod error;
 
 #[cfg(test)]
 mod tests;
 
 // Re-export commonly used types
-pub use models::attraction::{Attraction, Category, Location};
-pub use models::itinerary::{Itinerary, DayPlan, Visit, ConvergencePoint};
-pub use models::constraints::SolveParams;
+pub use error::Error;
+pub use models::attraction::{Attraction, Category, Location, TimeWindow};
+pub use models::itinerary::{Itinerary, DayPlan, Visit, AppointmentVisit, ConvergencePoint};
+pub use models::constraints::{Appointment, DayOverride, ParamError, SolveParams};
 pub use models::graph::{Graph, Edge};
-pub use algorithms::{greedy, simulated_annealing};
\ No newline at end of file
+pub use models::travel::{TravelTimeProvider, HaversineProvider, MatrixProvider};
+pub use models::transport::{TransportMode, TransportOptions, ModeProfile, Leg};
+pub use models::traffic::{TrafficModel, TimeDependentProvider};
+pub use models::calendar::HolidayCalendar;
+pub use algorithms::{alns, branch_and_bound, genetic, greedy, simulated_annealing};
+pub use algorithms::{Capabilities, DynSolver, Solver, SolverRegistry, StopToken};
+
+// Dates in `SolveParams` and `Attraction` are chrono types
+pub use chrono;
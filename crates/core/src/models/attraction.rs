@@ -1,4 +1,9 @@
+use crate::error::Error;
+use chrono::{Datelike, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum Category {
@@ -16,26 +21,309 @@ pub struct Location {
     pub lng: f64,
 }
 
+/// One opening period, `[open, close)` in minutes from midnight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimeWindow {
+    pub open: u32,
+    pub close: u32,
+}
+
+impl TimeWindow {
+    pub fn new(open: u32, close: u32) -> Self {
+        Self { open, close }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "AttractionRecord")]
 pub struct Attraction {
     pub id: u32,
     pub name: String,
     pub location: Location,
-    pub open_time: u32,      // minutes from midnight
-    pub close_time: u32,     // minutes from midnight
+    pub windows: Vec<TimeWindow>,  // sorted, non-overlapping opening periods
     pub duration: u32,       // expected visit time in minutes
     pub fee: f64,            // entrance fee in USD
     pub preference: f64,     // user preference score ∈ [0.0, 1.0]
     pub category: Category,
+    /// Replaces `windows` on the given weekdays; an empty list means closed.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub weekday_windows: HashMap<Weekday, Vec<TimeWindow>>,
+    /// Hours on public holidays; `None` keeps the usual hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holiday_windows: Option<Vec<TimeWindow>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub closed_dates: Vec<NaiveDate>,
+}
+
+/// Opening hours of an attraction on one particular day.
+#[derive(Debug, Clone, Copy)]
+pub struct DayHours<'a> {
+    windows: &'a [TimeWindow],
+    duration: u32,
+}
+
+/// Wire format that also accepts the older single `open_time`/`close_time`
+/// pair used by existing datasets.
+#[derive(Deserialize)]
+struct AttractionRecord {
+    id: u32,
+    name: String,
+    location: Location,
+    #[serde(default)]
+    windows: Vec<TimeWindow>,
+    #[serde(default)]
+    open_time: Option<u32>,
+    #[serde(default)]
+    close_time: Option<u32>,
+    duration: u32,
+    fee: f64,
+    preference: f64,
+    category: Category,
+    #[serde(default)]
+    weekday_windows: HashMap<Weekday, Vec<TimeWindow>>,
+    #[serde(default)]
+    holiday_windows: Option<Vec<TimeWindow>>,
+    #[serde(default)]
+    closed_dates: Vec<NaiveDate>,
+}
+
+/// Sort `windows` and reject empty, out-of-day or overlapping ones, which
+/// the solvers assume never happen.
+fn sort_windows(windows: &mut [TimeWindow], what: &str) -> Result<(), String> {
+    windows.sort_by_key(|w| w.open);
+    if let Some(w) = windows.iter().find(|w| w.open >= w.close || w.close > 1440) {
+        return Err(format!("{} window {}-{} must satisfy open < close <= 1440", what, w.open, w.close));
+    }
+    if let Some(pair) = windows.windows(2).find(|pair| pair[1].open < pair[0].close) {
+        return Err(format!(
+            "{} windows {}-{} and {}-{} overlap",
+            what, pair[0].open, pair[0].close, pair[1].open, pair[1].close
+        ));
+    }
+    Ok(())
+}
+
+impl TryFrom<AttractionRecord> for Attraction {
+    type Error = String;
+
+    fn try_from(record: AttractionRecord) -> Result<Self, String> {
+        let context = format!("Attraction {}", record.id);
+        let mut windows = record.windows;
+        if let (Some(open), Some(close)) = (record.open_time, record.close_time) {
+            windows.push(TimeWindow::new(open, close));
+        }
+        sort_windows(&mut windows, &context)?;
+
+        let mut weekday_windows = record.weekday_windows;
+        for (day, day_windows) in weekday_windows.iter_mut() {
+            sort_windows(day_windows, &format!("{} {}", context, day))?;
+        }
+        let mut holiday_windows = record.holiday_windows;
+        if let Some(day_windows) = holiday_windows.as_mut() {
+            sort_windows(day_windows, &format!("{} holiday", context))?;
+        }
+
+        Ok(Self {
+            id: record.id,
+            name: record.name,
+            location: record.location,
+            windows,
+            duration: record.duration,
+            fee: record.fee,
+            preference: record.preference,
+            category: record.category,
+            weekday_windows,
+            holiday_windows,
+            closed_dates: record.closed_dates,
+        })
+    }
+}
+
+impl<'a> DayHours<'a> {
+    pub fn windows(&self) -> &'a [TimeWindow] {
+        self.windows
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Earliest opening of the day.
+    pub fn opens_at(&self) -> Option<u32> {
+        self.windows.first().map(|w| w.open)
+    }
+
+    /// Latest closing of the day.
+    pub fn closes_at(&self) -> Option<u32> {
+        self.windows.iter().map(|w| w.close).max()
+    }
+
+    pub fn is_open_at(&self, time: u32) -> bool {
+        self.windows.iter().any(|w| time >= w.open && time < w.close)
+    }
+
+    /// Whether `[start, end]` lies entirely inside one opening window.
+    pub fn fits_window(&self, start: u32, end: u32) -> bool {
+        self.windows.iter().any(|w| start >= w.open && end <= w.close)
+    }
+
+    /// Earliest start at or after `arrival` for which a full visit fits in
+    /// a window, waiting for a later window if needed.
+    pub fn earliest_visit_start(&self, arrival: u32) -> Option<u32> {
+        self.windows
+            .iter()
+            .map(|w| arrival.max(w.open))
+            .find(|&start| self.fits_window(start, start + self.duration))
+    }
 }
 
 impl Attraction {
+    /// Regular hours, ignoring weekdays, holidays and closures.
+    pub fn hours(&self) -> DayHours<'_> {
+        DayHours { windows: &self.windows, duration: self.duration }
+    }
+
+    /// Hours on `date` (regular hours when the trip has no dates).
+    pub fn hours_on(&self, date: Option<NaiveDate>, holidays: &[NaiveDate]) -> DayHours<'_> {
+        let windows = match date {
+            None => &self.windows,
+            Some(d) if self.closed_dates.contains(&d) => &[][..],
+            Some(d) => match (&self.holiday_windows, holidays.contains(&d)) {
+                (Some(holiday), true) => holiday,
+                _ => self.weekday_windows.get(&d.weekday()).unwrap_or(&self.windows),
+            },
+        };
+        DayHours { windows, duration: self.duration }
+    }
+
+    pub fn opens_at(&self) -> Option<u32> {
+        self.hours().opens_at()
+    }
+
+    pub fn closes_at(&self) -> Option<u32> {
+        self.hours().closes_at()
+    }
+
     pub fn is_open_at(&self, time: u32) -> bool {
-        time >= self.open_time && time < self.close_time
+        self.hours().is_open_at(time)
     }
 
     pub fn can_visit_at(&self, arrival_time: u32) -> bool {
-        let departure_time = arrival_time + self.duration;
-        arrival_time >= self.open_time && departure_time <= self.close_time
+        self.fits_window(arrival_time, arrival_time + self.duration)
+    }
+
+    pub fn fits_window(&self, start: u32, end: u32) -> bool {
+        self.hours().fits_window(start, end)
+    }
+
+    pub fn earliest_visit_start(&self, arrival: u32) -> Option<u32> {
+        self.hours().earliest_visit_start(arrival)
     }
-}
\ No newline at end of file
+}
+
+/// Load a dataset file, rejecting duplicate attraction ids.
+pub fn load_dataset(path: &Path) -> Result<Vec<Attraction>, Error> {
+    let content = fs::read_to_string(path).map_err(|e| Error::read(path, e))?;
+    let attractions: Vec<Attraction> = serde_json::from_str(&content).map_err(|e| Error::parse(path, e))?;
+
+    let mut ids = HashSet::new();
+    if let Some(dup) = attractions.iter().find(|a| !ids.insert(a.id)) {
+        return Err(Error::dataset(path, format!("Duplicate attraction id {}", dup.id)));
+    }
+    Ok(attractions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temple() -> Attraction {
+        Attraction {
+            id: 1,
+            name: "Temple".to_string(),
+            location: Location { lat: 13.75, lng: 100.49 },
+            windows: vec![TimeWindow::new(480, 720), TimeWindow::new(780, 1020)],
+            duration: 60,
+            fee: 5.0,
+            preference: 0.8,
+            category: Category::Landmark,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_lunch_closure() {
+        let attr = temple();
+        assert!(attr.can_visit_at(600));
+        assert!(!attr.can_visit_at(690));  // would run into lunch
+        assert!(!attr.is_open_at(750));
+        assert!(attr.can_visit_at(780));
+    }
+
+    #[test]
+    fn test_earliest_visit_start_waits_for_next_window() {
+        let attr = temple();
+        assert_eq!(attr.earliest_visit_start(600), Some(600));
+        assert_eq!(attr.earliest_visit_start(690), Some(780));
+        assert_eq!(attr.earliest_visit_start(990), None);
+    }
+
+    #[test]
+    fn test_legacy_single_window_json() {
+        let json = r#"{
+            "id": 7, "name": "Museum", "location": {"lat": 13.75, "lng": 100.5},
+            "open_time": 540, "close_time": 1020, "duration": 90,
+            "fee": 10.0, "preference": 0.9, "category": "Museum"
+        }"#;
+        let attr: Attraction = serde_json::from_str(json).unwrap();
+        assert_eq!(attr.windows, vec![TimeWindow::new(540, 1020)]);
+    }
+
+    #[test]
+    fn test_bad_windows_are_rejected() {
+        let record = |windows: &str| {
+            format!(
+                r#"{{"id": 7, "name": "Museum", "location": {{"lat": 13.75, "lng": 100.5}},
+                    "duration": 60, "fee": 0.0, "preference": 0.5, "category": "Museum",
+                    {}}}"#,
+                windows
+            )
+        };
+
+        let ok = record(r#""windows": [{"open": 780, "close": 1020}, {"open": 480, "close": 720}]"#);
+        let attr: Attraction = serde_json::from_str(&ok).unwrap();
+        assert_eq!(attr.windows, vec![TimeWindow::new(480, 720), TimeWindow::new(780, 1020)]);
+
+        for bad in [
+            r#""open_time": 1020, "close_time": 540"#,
+            r#""windows": [{"open": 480, "close": 1500}]"#,
+            r#""windows": [{"open": 480, "close": 720}, {"open": 700, "close": 900}]"#,
+            r#""weekday_windows": {"Mon": [{"open": 600, "close": 600}]}"#,
+            r#""holiday_windows": [{"open": 480, "close": 720}, {"open": 600, "close": 800}]"#,
+        ] {
+            let err = serde_json::from_str::<Attraction>(&record(bad)).unwrap_err();
+            assert!(err.to_string().contains("Attraction 7"), "{}: {}", bad, err);
+        }
+    }
+
+    #[test]
+    fn test_hours_by_weekday_holiday_and_closure() {
+        let mut museum = temple();
+        museum.weekday_windows.insert(Weekday::Mon, Vec::new());
+        museum.holiday_windows = Some(vec![TimeWindow::new(600, 900)]);
+        let christmas = NaiveDate::from_ymd_opt(2026, 12, 25).unwrap();  // Friday
+        museum.closed_dates.push(christmas);
+
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+        let holidays = [tuesday];
+
+        assert!(museum.hours_on(Some(monday), &holidays).is_closed());
+        assert_eq!(museum.hours_on(Some(tuesday), &holidays).opens_at(), Some(600));
+        assert_eq!(museum.hours_on(Some(tuesday), &[]).opens_at(), Some(480));
+        assert!(museum.hours_on(Some(christmas), &holidays).is_closed());
+        assert_eq!(museum.hours_on(None, &holidays).windows(), &museum.windows[..]);
+    }
+}
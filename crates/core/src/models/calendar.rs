@@ -0,0 +1,68 @@
+use crate::error::Error;
+use crate::models::constraints::SolveParams;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// Public holidays, loaded from a JSON list of `{"date", "name"}` entries.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HolidayCalendar {
+    pub holidays: Vec<Holiday>,
+}
+
+impl HolidayCalendar {
+    pub fn from_json_file(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|e| Error::read(path, e))?;
+        serde_json::from_str(&content).map_err(|e| Error::parse(path, e))
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.iter().any(|h| h.date == date)
+    }
+
+    /// Add the holidays falling within the trip to `params.holidays`.
+    /// Trips without a start date are left untouched.
+    pub fn apply_to(&self, params: &mut SolveParams) {
+        let trip_dates: Vec<NaiveDate> = (1..=params.num_days)
+            .filter_map(|day| params.date_for_day(day))
+            .collect();
+
+        for date in trip_dates {
+            if self.is_holiday(date) && !params.holidays.contains(&date) {
+                params.holidays.push(date);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_to_trip_dates() {
+        let calendar: HolidayCalendar = serde_json::from_str(
+            r#"[{"date": "2026-04-13", "name": "Songkran"}, {"date": "2026-12-05", "name": "Father's Day"}]"#,
+        ).unwrap();
+
+        let mut params = SolveParams {
+            num_days: 3,
+            start_date: NaiveDate::from_ymd_opt(2026, 4, 12),
+            ..Default::default()
+        };
+        calendar.apply_to(&mut params);
+        assert_eq!(params.holidays, vec![NaiveDate::from_ymd_opt(2026, 4, 13).unwrap()]);
+
+        let mut undated = SolveParams::default();
+        calendar.apply_to(&mut undated);
+        assert!(undated.holidays.is_empty());
+    }
+}
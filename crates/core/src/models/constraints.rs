@@ -1,8 +1,15 @@
+use crate::models::attraction::Location;
+use crate::models::transport::{Leg, TransportOptions};
+use crate::models::travel::TravelTimeProvider;
+use chrono::{Days, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "utoipa")]
 use utoipa::ToSchema;
 
+const MINUTES_PER_DAY: u32 = 1440;
+const MAX_DAYS: u32 = 366;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(ToSchema))]
 pub struct SolveParams {
@@ -13,9 +20,66 @@ pub struct SolveParams {
     pub hotel_lat: f64,
     pub hotel_lng: f64,
     #[serde(default)]
-    pub end_lat: Option<f64>,     // optional different end location
+    pub end_lat: Option<f64>,     // where the last day ends, e.g. the airport
+    #[serde(default)]
+    pub end_lng: Option<f64>,     // None = back at the hotel
+    #[serde(default)]
+    pub transport: TransportOptions,
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,  // date of day 1; None = undated trip
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,       // public holidays during the trip
+    #[serde(default)]
+    pub day_overrides: Vec<DayOverride>,
+}
+
+/// Settings for one day that differ from the rest of the trip, e.g. a day
+/// that starts late after a flight, moves to a hotel in another city or
+/// ends wherever the last visit is.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct DayOverride {
+    pub day: u32,                 // 1-based
+    #[serde(default)]
+    pub hotel_lat: Option<f64>,   // hotel from this night on; the day ends here
+    #[serde(default)]
+    pub hotel_lng: Option<f64>,
+    #[serde(default)]
+    pub start_time: Option<u32>,
+    #[serde(default)]
+    pub daily_time_budget: Option<u32>,
+    #[serde(default)]
+    pub start_lat: Option<f64>,   // None = the hotel
     #[serde(default)]
-    pub end_lng: Option<f64>,     // optional different end location
+    pub start_lng: Option<f64>,
+    #[serde(default)]
+    pub end_lat: Option<f64>,     // None = as for the rest of the trip
+    #[serde(default)]
+    pub end_lng: Option<f64>,
+    /// The day ends at its last visit, with no closing leg.
+    #[serde(default)]
+    pub open_ended: bool,
+    /// Fixed commitments the plan works around, in time order.
+    #[serde(default)]
+    pub appointments: Vec<Appointment>,
+}
+
+/// A fixed block of the day spent at one place, e.g. a dinner booking or a
+/// meeting. The plan records the leg there, which counts like any other.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Appointment {
+    pub name: String,
+    pub lat: f64,
+    pub lng: f64,
+    pub start: u32,  // minutes from midnight
+    pub end: u32,
+}
+
+impl Appointment {
+    pub fn location(&self) -> Location {
+        Location { lat: self.lat, lng: self.lng }
+    }
 }
 
 impl Default for SolveParams {
@@ -29,6 +93,479 @@ impl Default for SolveParams {
             hotel_lng: 100.5018,
             end_lat: None,
             end_lng: None,
+            transport: TransportOptions::default(),
+            start_date: None,
+            holidays: Vec::new(),
+            day_overrides: Vec::new(),
+        }
+    }
+}
+
+impl SolveParams {
+    /// Calendar date of `day` (1-based), if the trip is dated.
+    pub fn date_for_day(&self, day: u32) -> Option<NaiveDate> {
+        self.start_date?.checked_add_days(Days::new(day.saturating_sub(1) as u64))
+    }
+
+    /// Minute `day` (1-based) starts.
+    pub fn day_start_time(&self, day: u32) -> u32 {
+        self.day_override(day).and_then(|o| o.start_time).unwrap_or(self.start_time)
+    }
+
+    /// Minutes available on `day` (1-based).
+    pub fn day_time_budget(&self, day: u32) -> u32 {
+        self.day_override(day).and_then(|o| o.daily_time_budget).unwrap_or(self.daily_time_budget)
+    }
+
+    /// Minute the time budget of `day` (1-based) runs out.
+    pub fn day_end(&self, day: u32) -> u32 {
+        self.day_start_time(day).saturating_add(self.day_time_budget(day))
+    }
+
+    pub fn hotel(&self) -> Location {
+        Location { lat: self.hotel_lat, lng: self.hotel_lng }
+    }
+
+    /// Hotel stayed at on the night after `day` (1-based): the latest
+    /// override up to that day that moves hotel, or the trip's hotel.
+    pub fn hotel_for_day(&self, day: u32) -> Location {
+        self.day_overrides
+            .iter()
+            .filter(|o| o.day <= day)
+            .filter_map(|o| Some((o.day, o.hotel_lat.zip(o.hotel_lng)?)))
+            .max_by_key(|&(d, _)| d)
+            .map_or(self.hotel(), |(_, (lat, lng))| Location { lat, lng })
+    }
+
+    fn day_override(&self, day: u32) -> Option<&DayOverride> {
+        self.day_overrides.iter().find(|o| o.day == day)
+    }
+
+    /// Fixed appointments on `day` (1-based), in time order.
+    pub fn appointments(&self, day: u32) -> &[Appointment] {
+        self.day_override(day).map_or(&[], |o| &o.appointments)
+    }
+
+    /// Where `day` (1-based) starts: the hotel of the night before (for day
+    /// 1, the trip's hotel) unless overridden.
+    pub fn day_start_location(&self, day: u32) -> Location {
+        match self.day_override(day).and_then(|o| o.start_lat.zip(o.start_lng)) {
+            Some((lat, lng)) => Location { lat, lng },
+            None if day <= 1 => self.hotel(),
+            None => self.hotel_for_day(day - 1),
         }
     }
-}
\ No newline at end of file
+
+    /// Where `day` (1-based) has to end, or `None` for an open-ended day.
+    /// Days end at that night's hotel, except the last one when
+    /// `end_lat`/`end_lng` are set; overrides take precedence over both.
+    pub fn day_end_location(&self, day: u32) -> Option<Location> {
+        let trip_end = match (self.end_lat.zip(self.end_lng), day == self.num_days) {
+            (Some((lat, lng)), true) => Location { lat, lng },
+            _ => self.hotel_for_day(day),
+        };
+        match self.day_override(day) {
+            Some(o) if o.open_ended => None,
+            Some(o) => Some(o.end_lat.zip(o.end_lng).map_or(trip_end, |(lat, lng)| Location { lat, lng })),
+            None => Some(trip_end),
+        }
+    }
+
+    /// Whether leaving `from` at `departure`, with the first `attended` of
+    /// the day's appointments behind, still makes the rest of them and
+    /// reaches the end of `day` before the time budget runs out (see
+    /// `finish_day`; open-ended days finish with the last stop). At most
+    /// `max_fare` is spent on the way.
+    pub fn can_end_day(
+        &self,
+        day: u32,
+        attended: usize,
+        from: &Location,
+        departure: u32,
+        max_fare: f64,
+        travel: &dyn TravelTimeProvider,
+    ) -> bool {
+        self.finish_day(day, attended, from, departure, max_fare, travel)
+            .is_some_and(|(_, finish)| finish <= self.day_end(day))
+    }
+
+    /// The leg from `from`, leaving at `time`, to where `day` (1-based)
+    /// ends; a zero-length one if the traveller is already there. `None` for
+    /// open-ended days or when no mode within `max_fare` gets there.
+    pub fn closing_leg(
+        &self,
+        day: u32,
+        from: &Location,
+        time: u32,
+        max_fare: f64,
+        travel: &dyn TravelTimeProvider,
+    ) -> Option<Leg> {
+        let end = self.day_end_location(day)?;
+        self.transport.choose_leg(from, &end, Some(time), max_fare, travel)
+    }
+
+    /// The leg from `from` at `time` to the next of `day`'s appointments,
+    /// the first `attended` being behind the traveller, with the time and
+    /// place they are free again. `None` if none is left or it cannot be
+    /// reached in time within `max_fare`.
+    pub fn attend_next(
+        &self,
+        day: u32,
+        attended: usize,
+        from: &Location,
+        time: u32,
+        max_fare: f64,
+        travel: &dyn TravelTimeProvider,
+    ) -> Option<(Leg, u32, Location)> {
+        let next = self.appointments(day).get(attended)?;
+        let leg = self.transport.choose_leg(from, &next.location(), Some(time), max_fare, travel)?;
+        (time + leg.travel_time <= next.start).then_some((leg, next.end, next.location()))
+    }
+
+    /// Fares and finishing time of the rest of `day` for a traveller free at
+    /// `from` at `time` with the first `attended` appointments behind them:
+    /// a leg to each appointment still ahead, then the closing leg (none on
+    /// open-ended days). `None` if an appointment cannot be made in time, or
+    /// the legs cost more than `max_fare` together.
+    pub fn finish_day(
+        &self,
+        day: u32,
+        attended: usize,
+        from: &Location,
+        time: u32,
+        max_fare: f64,
+        travel: &dyn TravelTimeProvider,
+    ) -> Option<(f64, u32)> {
+        let (mut from, mut time, mut fares) = (*from, time, 0.0);
+        for next in attended..self.appointments(day).len() {
+            let (leg, free_at, at) = self.attend_next(day, next, &from, time, max_fare - fares, travel)?;
+            (from, time, fares) = (at, free_at, fares + leg.fare);
+        }
+        if self.day_end_location(day).is_none() {
+            return Some((fares, time));
+        }
+        let leg = self.closing_leg(day, &from, time, max_fare - fares, travel)?;
+        Some((fares + leg.fare, time + leg.travel_time))
+    }
+
+    /// Every problem that would make a plan meaningless, e.g. no days, a
+    /// day running past midnight or a hotel left at (0, 0).
+    pub fn validate(&self) -> Result<(), Vec<ParamError>> {
+        let mut problems = Vec::new();
+        let mut problem = |field: String, message: String| problems.push(ParamError { field, message });
+
+        if self.num_days == 0 || self.num_days > MAX_DAYS {
+            problem("num_days".into(), format!("must be between 1 and {}, got {}", MAX_DAYS, self.num_days));
+        }
+        check_day_length("", self.start_time, self.daily_time_budget, &mut problem);
+        if !self.total_budget.is_finite() || self.total_budget < 0.0 {
+            problem("total_budget".into(), format!("must be a non-negative amount, got {}", self.total_budget));
+        }
+
+        check_coordinates("hotel_lat", "hotel_lng", self.hotel_lat, self.hotel_lng, &mut problem);
+        if self.hotel_lat == 0.0 && self.hotel_lng == 0.0 {
+            problem("hotel_lat".into(), "hotel is at (0, 0); set hotel_lat and hotel_lng".into());
+        }
+        check_optional_point("end_lat", "end_lng", self.end_lat, self.end_lng, &mut problem);
+
+        let mut override_days = Vec::new();
+        for (i, o) in self.day_overrides.iter().enumerate() {
+            let field = |name: &str| format!("day_overrides[{}].{}", i, name);
+            if o.day == 0 || o.day > self.num_days {
+                problem(field("day"), format!("must be between 1 and num_days ({}), got {}", self.num_days, o.day));
+            } else if override_days.contains(&o.day) {
+                problem(field("day"), format!("day {} is overridden more than once", o.day));
+            }
+            override_days.push(o.day);
+            if o.start_time.is_some() || o.daily_time_budget.is_some() {
+                let start = o.start_time.unwrap_or(self.start_time);
+                let budget = o.daily_time_budget.unwrap_or(self.daily_time_budget);
+                check_day_length(&field(""), start, budget, &mut problem);
+            }
+            check_optional_point(&field("hotel_lat"), &field("hotel_lng"), o.hotel_lat, o.hotel_lng, &mut problem);
+            check_optional_point(&field("start_lat"), &field("start_lng"), o.start_lat, o.start_lng, &mut problem);
+            check_optional_point(&field("end_lat"), &field("end_lng"), o.end_lat, o.end_lng, &mut problem);
+            if o.open_ended && (o.end_lat.is_some() || o.end_lng.is_some()) {
+                problem(field("open_ended"), "an open-ended day cannot also set end_lat/end_lng".into());
+            }
+
+            let day_start = o.start_time.unwrap_or(self.start_time);
+            let day_end = day_start.saturating_add(o.daily_time_budget.unwrap_or(self.daily_time_budget));
+            let mut free_from = day_start;
+            for (j, appointment) in o.appointments.iter().enumerate() {
+                let field = |name: &str| format!("day_overrides[{}].appointments[{}].{}", i, j, name);
+                check_coordinates(&field("lat"), &field("lng"), appointment.lat, appointment.lng, &mut problem);
+                if appointment.start >= appointment.end {
+                    problem(
+                        field("end"),
+                        format!("must be after start ({}), got {}", appointment.start, appointment.end),
+                    );
+                } else if appointment.start < free_from || appointment.end > day_end {
+                    problem(
+                        field("start"),
+                        format!(
+                            "{}-{} must fall within the day ({}-{}) and after the previous appointment",
+                            appointment.start, appointment.end, day_start, day_end
+                        ),
+                    );
+                }
+                free_from = free_from.max(appointment.end);
+            }
+        }
+
+        if self.transport.modes.is_empty() {
+            problem("transport.modes".into(), "must list at least one mode".into());
+        }
+        for (i, mode) in self.transport.modes.iter().enumerate() {
+            let field = |name: &str| format!("transport.modes[{}].{}", i, name);
+            if mode.speed_kmh.is_some_and(|speed| !speed.is_finite() || speed <= 0.0) {
+                problem(field("speed_kmh"), "must be positive".into());
+            }
+            if !mode.base_fare.is_finite() || mode.base_fare < 0.0 {
+                problem(field("base_fare"), "must be a non-negative amount".into());
+            }
+            if !mode.fare_per_km.is_finite() || mode.fare_per_km < 0.0 {
+                problem(field("fare_per_km"), "must be a non-negative amount".into());
+            }
+            if mode.max_distance_km.is_some_and(|km| km.is_nan() || km <= 0.0) {
+                problem(field("max_distance_km"), "must be positive".into());
+            }
+            if mode.available_from >= mode.available_until || mode.available_until > MINUTES_PER_DAY {
+                problem(
+                    field("available_until"),
+                    format!(
+                        "service hours {}-{} must satisfy available_from < available_until <= 1440",
+                        mode.available_from, mode.available_until
+                    ),
+                );
+            }
+        }
+        if !self.transport.value_of_time.is_finite() || self.transport.value_of_time < 0.0 {
+            problem("transport.value_of_time".into(), "must be a non-negative amount".into());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// One problem found by `SolveParams::validate`, located by its field path,
+/// e.g. `transport.modes[1].speed_kmh`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ParamError {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+fn check_coordinates(
+    lat_field: &str,
+    lng_field: &str,
+    lat: f64,
+    lng: f64,
+    problem: &mut impl FnMut(String, String),
+) {
+    if !(-90.0..=90.0).contains(&lat) {
+        problem(lat_field.into(), format!("must be a latitude between -90 and 90, got {}", lat));
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        problem(lng_field.into(), format!("must be a longitude between -180 and 180, got {}", lng));
+    }
+}
+
+/// A start time and time budget that keep the day before midnight; `prefix`
+/// locates the fields, e.g. `day_overrides[0].`.
+fn check_day_length(prefix: &str, start_time: u32, budget: u32, problem: &mut impl FnMut(String, String)) {
+    if start_time >= MINUTES_PER_DAY {
+        problem(format!("{}start_time", prefix), format!("must be before midnight (< 1440), got {}", start_time));
+    }
+    if budget == 0 {
+        problem(format!("{}daily_time_budget", prefix), "must be positive".into());
+    } else if start_time < MINUTES_PER_DAY && start_time.saturating_add(budget) > MINUTES_PER_DAY {
+        problem(
+            format!("{}daily_time_budget", prefix),
+            format!(
+                "day would end at minute {}, past midnight; start_time + daily_time_budget must be at most 1440",
+                start_time.saturating_add(budget)
+            ),
+        );
+    }
+}
+
+/// Coordinates that are optional but only make sense as a pair.
+fn check_optional_point(
+    lat_field: &str,
+    lng_field: &str,
+    lat: Option<f64>,
+    lng: Option<f64>,
+    problem: &mut impl FnMut(String, String),
+) {
+    match (lat, lng) {
+        (Some(lat), Some(lng)) => check_coordinates(lat_field, lng_field, lat, lng, problem),
+        (Some(_), None) => problem(lng_field.into(), format!("must be set along with {}", lat_field)),
+        (None, Some(_)) => problem(lat_field.into(), format!("must be set along with {}", lng_field)),
+        (None, None) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_params_are_valid() {
+        assert_eq!(SolveParams::default().validate(), Ok(()));
+        let bangkok = SolveParams { transport: TransportOptions::bangkok(), ..Default::default() };
+        assert_eq!(bangkok.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_every_problem_is_reported() {
+        let mut params = SolveParams {
+            num_days: 0,
+            start_time: 1500,
+            total_budget: -5.0,
+            hotel_lat: 0.0,
+            hotel_lng: 0.0,
+            end_lat: Some(13.69),
+            ..Default::default()
+        };
+        params.transport.modes[0].speed_kmh = Some(0.0);
+
+        let fields: Vec<String> = params.validate().unwrap_err().into_iter().map(|p| p.field).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "num_days",
+                "start_time",
+                "total_budget",
+                "hotel_lat",
+                "end_lng",
+                "transport.modes[0].speed_kmh",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_day_past_midnight_is_rejected() {
+        let params = SolveParams { start_time: 1200, daily_time_budget: 300, ..Default::default() };
+        let problems = params.validate().unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "daily_time_budget");
+    }
+
+    #[test]
+    fn test_day_endpoints() {
+        let airport = (13.69, 100.75);
+        let params = SolveParams {
+            num_days: 3,
+            end_lat: Some(airport.0),
+            end_lng: Some(airport.1),
+            day_overrides: vec![
+                DayOverride { day: 1, start_lat: Some(13.75), start_lng: Some(100.52), ..Default::default() },
+                DayOverride { day: 2, open_ended: true, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        let hotel = params.hotel();
+        assert_eq!(params.day_start_location(1).lat, 13.75);
+        assert_eq!(params.day_start_location(2).lat, hotel.lat);
+        assert_eq!(params.day_end_location(1).map(|l| l.lat), Some(hotel.lat));
+        assert!(params.day_end_location(2).is_none());
+        assert_eq!(params.day_end_location(3).map(|l| l.lng), Some(airport.1));
+    }
+
+    #[test]
+    fn test_bad_day_overrides_are_rejected() {
+        let params = SolveParams {
+            day_overrides: vec![
+                DayOverride { day: 3, ..Default::default() },
+                DayOverride { day: 1, start_lat: Some(13.75), ..Default::default() },
+                DayOverride { day: 1, end_lat: Some(13.7), end_lng: Some(100.5), open_ended: true, ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let fields: Vec<String> = params.validate().unwrap_err().into_iter().map(|p| p.field).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "day_overrides[0].day",
+                "day_overrides[1].start_lng",
+                "day_overrides[2].day",
+                "day_overrides[2].open_ended",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hotel_moves_for_the_rest_of_the_trip() {
+        let ayutthaya = (14.3532, 100.5689);
+        let params = SolveParams {
+            num_days: 4,
+            day_overrides: vec![
+                DayOverride { day: 1, start_time: Some(840), daily_time_budget: Some(300), ..Default::default() },
+                DayOverride { day: 3, hotel_lat: Some(ayutthaya.0), hotel_lng: Some(ayutthaya.1), ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        assert_eq!((params.day_start_time(1), params.day_end(1)), (840, 1140));
+        assert_eq!((params.day_start_time(2), params.day_end(2)), (540, 1140));
+        // Day 3 checks out in Bangkok and ends at the new hotel
+        assert_eq!(params.day_start_location(3).lat, params.hotel_lat);
+        assert_eq!(params.day_end_location(3).map(|l| l.lat), Some(ayutthaya.0));
+        assert_eq!(params.day_start_location(4).lat, ayutthaya.0);
+        assert_eq!(params.day_end_location(4).map(|l| l.lat), Some(ayutthaya.0));
+
+        // Moving on day 1 still starts the trip at its own hotel
+        let first_night = SolveParams {
+            day_overrides: vec![DayOverride {
+                day: 1,
+                hotel_lat: Some(ayutthaya.0),
+                hotel_lng: Some(ayutthaya.1),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(first_night.day_start_location(1).lat, first_night.hotel_lat);
+        assert_eq!(first_night.day_end_location(1).map(|l| l.lat), Some(ayutthaya.0));
+        assert_eq!(first_night.day_start_location(2).lat, ayutthaya.0);
+    }
+
+    #[test]
+    fn test_bad_day_settings_are_rejected() {
+        let appointment = |start, end| Appointment { name: "Dinner".into(), lat: 13.74, lng: 100.55, start, end };
+        let params = SolveParams {
+            day_overrides: vec![
+                DayOverride { day: 1, start_time: Some(1200), ..Default::default() },
+                DayOverride {
+                    day: 2,
+                    appointments: vec![appointment(600, 660), appointment(640, 700), appointment(800, 790)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let fields: Vec<String> = params.validate().unwrap_err().into_iter().map(|p| p.field).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "day_overrides[0].daily_time_budget",
+                "day_overrides[1].appointments[1].start",
+                "day_overrides[1].appointments[2].end",
+            ]
+        );
+    }
+}
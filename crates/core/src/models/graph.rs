@@ -1,14 +1,14 @@
 use std::collections::HashMap;
 use crate::models::attraction::Attraction;
-use crate::utils::distance::haversine_distance;
-
-const CITY_SPEED_KMH: f64 = 30.0;
+use crate::models::transport::{TransportMode, TransportOptions};
+use crate::models::travel::{HaversineProvider, TravelTimeProvider};
 
 #[derive(Debug, Clone)]
 pub struct Edge {
     pub to: u32,
     pub travel_time: u32,  // minutes
-    pub cost: f64,
+    pub cost: f64,         // fare in USD
+    pub mode: TransportMode,
 }
 
 #[derive(Debug, Clone)]
@@ -18,24 +18,35 @@ pub struct Graph {
 
 impl Graph {
     pub fn from_attractions(attractions: &[Attraction]) -> Self {
+        Self::from_attractions_with(attractions, &HaversineProvider::default())
+    }
+
+    pub fn from_attractions_with(attractions: &[Attraction], travel: &dyn TravelTimeProvider) -> Self {
+        Self::from_attractions_with_transport(attractions, travel, &TransportOptions::default())
+    }
+
+    /// Edges carry the preferred mode between each pair, ignoring service
+    /// hours since the departure time is not known here.
+    pub fn from_attractions_with_transport(
+        attractions: &[Attraction],
+        travel: &dyn TravelTimeProvider,
+        transport: &TransportOptions,
+    ) -> Self {
         let mut adj = HashMap::new();
         
         for from in attractions {
             let mut edges = Vec::new();
             for to in attractions {
                 if from.id != to.id {
-                    let distance_km = haversine_distance(
-                        from.location.lat,
-                        from.location.lng,
-                        to.location.lat,
-                        to.location.lng,
-                    );
-                    let travel_time = ((distance_km / CITY_SPEED_KMH) * 60.0).ceil() as u32;
-                    edges.push(Edge {
-                        to: to.id,
-                        travel_time,
-                        cost: 0.0,  // no travel cost in this model
-                    });
+                    let leg = transport.choose_leg(&from.location, &to.location, None, f64::INFINITY, travel);
+                    if let Some(leg) = leg {
+                        edges.push(Edge {
+                            to: to.id,
+                            travel_time: leg.travel_time,
+                            cost: leg.fare,
+                            mode: leg.mode,
+                        });
+                    }
                 }
             }
             adj.insert(from.id, edges);
@@ -54,4 +65,4 @@ impl Graph {
     pub fn neighbors(&self, node: u32) -> Option<&Vec<Edge>> {
         self.adj.get(&node)
     }
-}
\ No newline at end of file
+}
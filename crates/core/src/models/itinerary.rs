@@ -1,3 +1,7 @@
+use crate::models::attraction::Location;
+use crate::models::constraints::{Appointment, SolveParams};
+use crate::models::transport::Leg;
+use crate::models::travel::TravelTimeProvider;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "utoipa")]
@@ -8,11 +12,38 @@ use utoipa::ToSchema;
 pub struct Visit {
     pub attraction_id: u32,
     pub attraction_name: String,
-    pub arrival_time: u32,
+    pub arrival_time: u32,  // start of the visit, after any waiting
     pub departure_time: u32,
     pub fee: f64,
     pub preference: f64,
     pub category: String,
+    pub leg: Leg,  // how the traveller arrived
+    #[serde(default)]
+    pub wait_minutes: u32,  // idle time on site before arrival_time
+}
+
+/// One of the day's appointments, as kept by the plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct AppointmentVisit {
+    pub name: String,
+    pub start: u32,
+    pub end: u32,
+    pub leg: Leg,  // how the traveller got there
+    pub arrival_time: u32,  // at or before `start`
+}
+
+impl AppointmentVisit {
+    /// `appointment`, reached by `leg` leaving at `left_at`.
+    pub fn new(appointment: &Appointment, leg: Leg, left_at: u32) -> Self {
+        Self {
+            name: appointment.name.clone(),
+            start: appointment.start,
+            end: appointment.end,
+            leg,
+            arrival_time: left_at + leg.travel_time,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,9 +51,60 @@ pub struct Visit {
 pub struct DayPlan {
     pub day: u32,
     pub visits: Vec<Visit>,
-    pub total_travel_time: u32,
-    pub total_cost: f64,
+    /// The day's appointments in time order, with how each was reached.
+    #[serde(default)]
+    pub appointments: Vec<AppointmentVisit>,
+    pub total_travel_time: u32,  // including appointment and return legs
+    pub total_cost: f64,   // entrance fees plus fares
+    #[serde(default)]
+    pub total_fare: f64,
     pub total_satisfaction: f64,
+    /// How the traveller gets from the last stop to where the day ends;
+    /// `None` for open-ended days.
+    #[serde(default)]
+    pub return_leg: Option<Leg>,
+    #[serde(default)]
+    pub return_arrival_time: Option<u32>,
+}
+
+impl DayPlan {
+    /// Add the next of the day's appointments, reached by `leg` leaving at
+    /// `left_at`, to the plan and its totals.
+    pub fn attend(&mut self, params: &SolveParams, leg: Leg, left_at: u32) {
+        let appointment = &params.appointments(self.day)[self.appointments.len()];
+        self.appointments.push(AppointmentVisit::new(appointment, leg, left_at));
+        self.total_travel_time += leg.travel_time;
+        self.total_fare += leg.fare;
+        self.total_cost += leg.fare;
+    }
+
+    /// Finish the day for a traveller free at `from` at `time`: attend the
+    /// appointments still ahead, then take the leg to where the day ends
+    /// (see `SolveParams::closing_leg`), adding each to the plan and its
+    /// totals. At most `max_fare` is spent.
+    pub fn close(
+        &mut self,
+        params: &SolveParams,
+        from: &Location,
+        time: u32,
+        max_fare: f64,
+        travel: &dyn TravelTimeProvider,
+    ) {
+        let (mut from, mut time, mut spent) = (*from, time, 0.0);
+        while let Some((leg, free_at, at)) =
+            params.attend_next(self.day, self.appointments.len(), &from, time, max_fare - spent, travel)
+        {
+            self.attend(params, leg, time);
+            (from, time, spent) = (at, free_at, spent + leg.fare);
+        }
+        if let Some(leg) = params.closing_leg(self.day, &from, time, max_fare - spent, travel) {
+            self.return_leg = Some(leg);
+            self.return_arrival_time = Some(time + leg.travel_time);
+            self.total_travel_time += leg.travel_time;
+            self.total_fare += leg.fare;
+            self.total_cost += leg.fare;
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,8 +124,11 @@ pub struct Itinerary {
 #[cfg_attr(feature = "utoipa", derive(ToSchema))]
 pub struct ConvergencePoint {
     pub iteration: u32,
-    pub satisfaction: f64,
-    pub temperature: f64,
+    pub satisfaction: f64,  // best so far
+    pub temperature: f64,   // 0 for methods without one
+    /// Mean fitness of the population, for population-based methods.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean_fitness: Option<f64>,
 }
 
 impl Itinerary {
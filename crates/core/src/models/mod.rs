@@ -2,8 +2,16 @@ pub mod attraction;
 pub mod itinerary;
 pub mod graph;
 pub mod constraints;
+pub mod travel;
+pub mod transport;
+pub mod traffic;
+pub mod calendar;
 
-pub use attraction::{Attraction, Location, Category};
-pub use itinerary::{Itinerary, DayPlan, Visit};
+pub use attraction::{load_dataset, Attraction, Location, Category, TimeWindow, DayHours};
+pub use itinerary::{Itinerary, DayPlan, Visit, AppointmentVisit};
 pub use graph::{Graph, Edge};
-pub use constraints::SolveParams;
+pub use constraints::{Appointment, DayOverride, ParamError, SolveParams};
+pub use travel::{load_travel_model, TravelTimeProvider, HaversineProvider, MatrixProvider, UNREACHABLE};
+pub use transport::{TransportMode, TransportOptions, ModeProfile, Leg};
+pub use traffic::{TrafficModel, TrafficProfile, TimeDependentProvider};
+pub use calendar::{HolidayCalendar, Holiday};
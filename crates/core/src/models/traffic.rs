@@ -0,0 +1,234 @@
+use crate::error::Error;
+use crate::models::attraction::Location;
+use crate::models::travel::{TravelTimeProvider, UNREACHABLE};
+use crate::utils::distance::haversine_distance;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const MINUTES_PER_DAY: u32 = 1440;
+
+/// Travel takes `factor` times longer than free flow between `start` and
+/// `end` (minutes from midnight).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CongestionPeriod {
+    pub start: u32,
+    pub end: u32,
+    pub factor: f64,
+}
+
+/// Slow-downs by time of day; free flow (factor 1.0) outside any period.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrafficProfile {
+    pub periods: Vec<CongestionPeriod>,
+}
+
+/// A profile that applies to legs whose midpoint falls within `radius_km`
+/// of `center`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficZone {
+    pub name: String,
+    pub center: Location,
+    pub radius_km: f64,
+    pub profile: TrafficProfile,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrafficModel {
+    #[serde(default)]
+    pub default: TrafficProfile,
+    #[serde(default)]
+    pub zones: Vec<TrafficZone>,
+}
+
+impl TrafficProfile {
+    /// Factor in effect at `time` and the minute at which it next changes.
+    fn factor_at(&self, time: u32) -> (f64, u32) {
+        let day_start = time - time % MINUTES_PER_DAY;
+        let minute = time % MINUTES_PER_DAY;
+
+        let mut next_change = MINUTES_PER_DAY;
+        let mut factor = 1.0;
+        for period in &self.periods {
+            if minute >= period.start && minute < period.end {
+                factor = period.factor;
+                next_change = next_change.min(period.end);
+            } else if period.start > minute {
+                next_change = next_change.min(period.start);
+            }
+        }
+
+        (factor, day_start + next_change)
+    }
+
+    /// Time to cover `free_flow` minutes of driving when leaving at
+    /// `departure`. The traveller progresses at `1 / factor` of free-flow
+    /// speed in each period, so leaving later never means arriving earlier
+    /// (the FIFO property).
+    pub fn travel_time(&self, free_flow: u32, departure: u32) -> u32 {
+        if self.periods.is_empty() || free_flow == 0 {
+            return free_flow;
+        }
+
+        let mut remaining = free_flow as f64;
+        let mut now = departure as f64;
+        loop {
+            let (factor, boundary) = self.factor_at(now.floor() as u32);
+            let capacity = (boundary as f64 - now) / factor;
+            if remaining <= capacity {
+                now += remaining * factor;
+                break;
+            }
+            remaining -= capacity;
+            now = boundary as f64;
+        }
+
+        (now - departure as f64 - 1e-9).ceil().max(0.0) as u32
+    }
+
+    fn validate(&self, context: &str) -> Result<(), String> {
+        for period in &self.periods {
+            if period.start >= period.end || period.end > MINUTES_PER_DAY {
+                return Err(format!(
+                    "{}: congestion period {}-{} must satisfy start < end <= 1440",
+                    context, period.start, period.end
+                ));
+            }
+            if period.factor.is_nan() || period.factor <= 0.0 {
+                return Err(format!(
+                    "{}: congestion factor must be positive, got {}",
+                    context, period.factor
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TrafficModel {
+    /// Morning and evening rush hours with Sukhumvit slower than the rest
+    /// of the city.
+    pub fn bangkok() -> Self {
+        let rush = |factor_am: f64, factor_pm: f64| TrafficProfile {
+            periods: vec![
+                CongestionPeriod { start: 420, end: 570, factor: factor_am },   // 07:00-09:30
+                CongestionPeriod { start: 990, end: 1170, factor: factor_pm },  // 16:30-19:30
+            ],
+        };
+
+        Self {
+            default: rush(1.5, 1.6),
+            zones: vec![TrafficZone {
+                name: "Sukhumvit".to_string(),
+                center: Location { lat: 13.7370, lng: 100.5600 },
+                radius_km: 3.0,
+                profile: rush(2.0, 2.2),
+            }],
+        }
+    }
+
+    pub fn from_json_file(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|e| Error::read(path, e))?;
+        let model: Self = serde_json::from_str(&content).map_err(|e| Error::parse(path, e))?;
+
+        model.default.validate("default").map_err(|e| Error::dataset(path, e))?;
+        for zone in &model.zones {
+            zone.profile.validate(&zone.name).map_err(|e| Error::dataset(path, e))?;
+        }
+        Ok(model)
+    }
+
+    pub fn profile_for(&self, from: &Location, to: &Location) -> &TrafficProfile {
+        let mid = Location {
+            lat: (from.lat + to.lat) / 2.0,
+            lng: (from.lng + to.lng) / 2.0,
+        };
+
+        self.zones
+            .iter()
+            .map(|z| (z, haversine_distance(mid.lat, mid.lng, z.center.lat, z.center.lng)))
+            .filter(|(z, d)| *d <= z.radius_km)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(z, _)| &z.profile)
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Scales another provider's free-flow times by a `TrafficModel`.
+pub struct TimeDependentProvider {
+    inner: Box<dyn TravelTimeProvider>,
+    traffic: TrafficModel,
+}
+
+impl TimeDependentProvider {
+    pub fn new(inner: Box<dyn TravelTimeProvider>, traffic: TrafficModel) -> Self {
+        Self { inner, traffic }
+    }
+}
+
+impl TravelTimeProvider for TimeDependentProvider {
+    fn travel_time(&self, from: &Location, to: &Location) -> u32 {
+        self.inner.travel_time(from, to)
+    }
+
+    fn travel_time_at(&self, from: &Location, to: &Location, departure: u32) -> u32 {
+        let free_flow = self.inner.travel_time_at(from, to, departure);
+        if free_flow == UNREACHABLE {
+            return UNREACHABLE;
+        }
+        self.traffic.profile_for(from, to).travel_time(free_flow, departure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::travel::HaversineProvider;
+
+    fn rush_hour() -> TrafficProfile {
+        TrafficProfile {
+            periods: vec![CongestionPeriod { start: 420, end: 570, factor: 2.0 }],
+        }
+    }
+
+    #[test]
+    fn test_free_flow_outside_periods() {
+        assert_eq!(rush_hour().travel_time(30, 600), 30);
+        assert_eq!(TrafficProfile::default().travel_time(30, 480), 30);
+    }
+
+    #[test]
+    fn test_slowdown_inside_and_across_boundary() {
+        let profile = rush_hour();
+        assert_eq!(profile.travel_time(30, 480), 60);
+        // 10 minutes of rush hour cover 5 free-flow minutes, the other 25 run free
+        assert_eq!(profile.travel_time(30, 560), 35);
+        // Entering the rush: 10 free minutes, then 20 at half speed
+        assert_eq!(profile.travel_time(30, 410), 50);
+    }
+
+    #[test]
+    fn test_fifo_property() {
+        let profile = TrafficModel::bangkok().default;
+        let mut last_arrival = 0;
+        for departure in 360..1260 {
+            let arrival = departure + profile.travel_time(45, departure);
+            assert!(arrival >= last_arrival, "FIFO violated at {}", departure);
+            last_arrival = arrival;
+        }
+    }
+
+    #[test]
+    fn test_zone_overrides_default() {
+        let provider = TimeDependentProvider::new(
+            Box::new(HaversineProvider::default()),
+            TrafficModel::bangkok(),
+        );
+        let asok = Location { lat: 13.7370, lng: 100.5600 };
+        let phrom_phong = Location { lat: 13.7305, lng: 100.5697 };
+
+        let free = provider.travel_time(&asok, &phrom_phong);
+        assert_eq!(provider.travel_time_at(&asok, &phrom_phong, 600), free);
+        assert!(provider.travel_time_at(&asok, &phrom_phong, 480) >= free * 2);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::models::attraction::Location;
+use crate::models::travel::{TravelTimeProvider, UNREACHABLE};
+use crate::utils::distance::haversine_distance;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub enum TransportMode {
+    Walk,
+    Taxi,
+    Rail,
+    Boat,
+}
+
+/// Speed, fare and service hours of one way of getting around.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct ModeProfile {
+    pub mode: TransportMode,
+    #[serde(default)]
+    pub speed_kmh: Option<f64>,       // None = use the road travel-time provider
+    #[serde(default)]
+    pub access_minutes: u32,          // hailing, walking to the station, waiting
+    #[serde(default)]
+    pub base_fare: f64,               // USD per leg
+    #[serde(default)]
+    pub fare_per_km: f64,             // USD per straight-line km
+    #[serde(default)]
+    pub max_distance_km: Option<f64>,
+    #[serde(default)]
+    pub available_from: u32,          // minutes from midnight
+    #[serde(default = "end_of_day")]
+    pub available_until: u32,         // minutes from midnight
+}
+
+fn end_of_day() -> u32 {
+    1440
+}
+
+/// How the traveller got to a stop.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Leg {
+    pub mode: TransportMode,
+    pub travel_time: u32,  // minutes
+    pub fare: f64,         // USD
+}
+
+/// Modes the traveller is willing to use and how they trade money for time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct TransportOptions {
+    pub modes: Vec<ModeProfile>,
+    #[serde(default)]
+    pub value_of_time: f64,  // USD per hour of travel
+}
+
+impl Default for TransportOptions {
+    /// A single fare-free road mode, i.e. travel time straight from the
+    /// provider with no money involved.
+    fn default() -> Self {
+        Self {
+            modes: vec![ModeProfile {
+                mode: TransportMode::Taxi,
+                speed_kmh: None,
+                access_minutes: 0,
+                base_fare: 0.0,
+                fare_per_km: 0.0,
+                max_distance_km: None,
+                available_from: 0,
+                available_until: end_of_day(),
+            }],
+            value_of_time: 0.0,
+        }
+    }
+}
+
+impl ModeProfile {
+    pub fn is_available_at(&self, time: u32) -> bool {
+        time >= self.available_from && time < self.available_until
+    }
+
+    /// Leg by this mode, or `None` if it is out of range or the provider has
+    /// no route. Road modes follow the provider, including congestion at
+    /// `departure` when given.
+    pub fn leg(
+        &self,
+        from: &Location,
+        to: &Location,
+        departure: Option<u32>,
+        travel: &dyn TravelTimeProvider,
+    ) -> Option<Leg> {
+        let distance_km = haversine_distance(from.lat, from.lng, to.lat, to.lng);
+        if distance_km == 0.0 {
+            return Some(Leg { mode: self.mode, travel_time: 0, fare: 0.0 });
+        }
+        if self.max_distance_km.is_some_and(|max| distance_km > max) {
+            return None;
+        }
+
+        let moving_time = match self.speed_kmh {
+            Some(speed) => ((distance_km / speed) * 60.0).ceil() as u32,
+            None => match departure {
+                Some(t) => travel.travel_time_at(from, to, t + self.access_minutes),
+                None => travel.travel_time(from, to),
+            },
+        };
+        if moving_time == UNREACHABLE {
+            return None;
+        }
+
+        Some(Leg {
+            mode: self.mode,
+            travel_time: self.access_minutes + moving_time,
+            fare: self.base_fare + self.fare_per_km * distance_km,
+        })
+    }
+}
+
+impl TransportOptions {
+    /// Typical Bangkok options: walking, metered taxi, BTS/MRT and the
+    /// Chao Phraya express boat.
+    pub fn bangkok() -> Self {
+        Self {
+            modes: vec![
+                ModeProfile {
+                    mode: TransportMode::Walk,
+                    speed_kmh: Some(4.5),
+                    access_minutes: 0,
+                    base_fare: 0.0,
+                    fare_per_km: 0.0,
+                    max_distance_km: Some(2.5),
+                    available_from: 0,
+                    available_until: end_of_day(),
+                },
+                ModeProfile {
+                    mode: TransportMode::Taxi,
+                    speed_kmh: None,
+                    access_minutes: 5,
+                    base_fare: 1.0,
+                    fare_per_km: 0.25,
+                    max_distance_km: None,
+                    available_from: 0,
+                    available_until: end_of_day(),
+                },
+                ModeProfile {
+                    mode: TransportMode::Rail,
+                    speed_kmh: Some(35.0),
+                    access_minutes: 12,
+                    base_fare: 0.5,
+                    fare_per_km: 0.1,
+                    max_distance_km: None,
+                    available_from: 330,   // 05:30
+                    available_until: 1440, // midnight
+                },
+                ModeProfile {
+                    mode: TransportMode::Boat,
+                    speed_kmh: Some(15.0),
+                    access_minutes: 10,
+                    base_fare: 0.5,
+                    fare_per_km: 0.0,
+                    max_distance_km: Some(10.0),
+                    available_from: 360,   // 06:00
+                    available_until: 1140, // 19:00
+                },
+            ],
+            value_of_time: 5.0,
+        }
+    }
+
+    /// Cheapest leg by generalized cost (fare plus time valued at
+    /// `value_of_time`) among modes running at `departure` whose fare fits
+    /// in `max_fare`. `departure = None` ignores service hours.
+    pub fn choose_leg(
+        &self,
+        from: &Location,
+        to: &Location,
+        departure: Option<u32>,
+        max_fare: f64,
+        travel: &dyn TravelTimeProvider,
+    ) -> Option<Leg> {
+        // Staying put needs no service running
+        self.modes
+            .iter()
+            .filter_map(|m| Some((m, m.leg(from, to, departure, travel)?)))
+            .filter(|(m, leg)| leg.travel_time == 0 || departure.is_none_or(|t| m.is_available_at(t)))
+            .map(|(_, leg)| leg)
+            .filter(|leg| leg.fare <= max_fare)
+            .min_by(|a, b| {
+                let cost_a = a.fare + a.travel_time as f64 * self.value_of_time / 60.0;
+                let cost_b = b.fare + b.travel_time as f64 * self.value_of_time / 60.0;
+                cost_a
+                    .total_cmp(&cost_b)
+                    .then(a.travel_time.cmp(&b.travel_time))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::travel::HaversineProvider;
+
+    fn loc(lat: f64, lng: f64) -> Location {
+        Location { lat, lng }
+    }
+
+    #[test]
+    fn test_default_matches_provider() {
+        let travel = HaversineProvider::default();
+        let (a, b) = (loc(13.75, 100.50), loc(13.78, 100.53));
+        let leg = TransportOptions::default()
+            .choose_leg(&a, &b, Some(600), 0.0, &travel)
+            .unwrap();
+
+        assert_eq!(leg.travel_time, travel.travel_time(&a, &b));
+        assert_eq!(leg.fare, 0.0);
+    }
+
+    #[test]
+    fn test_short_hop_walks() {
+        let travel = HaversineProvider::default();
+        let (a, b) = (loc(13.750, 100.500), loc(13.752, 100.501));
+        let leg = TransportOptions::bangkok()
+            .choose_leg(&a, &b, Some(600), 100.0, &travel)
+            .unwrap();
+
+        assert_eq!(leg.mode, TransportMode::Walk);
+    }
+
+    #[test]
+    fn test_budget_and_hours_restrict_modes() {
+        let travel = HaversineProvider::default();
+        let (a, b) = (loc(13.70, 100.45), loc(13.80, 100.55));
+        let options = TransportOptions::bangkok();
+
+        // Too far to walk and nothing affordable
+        assert!(options.choose_leg(&a, &b, Some(600), 0.0, &travel).is_none());
+
+        // At 02:00 only taxis run
+        let leg = options.choose_leg(&a, &b, Some(120), 100.0, &travel).unwrap();
+        assert_eq!(leg.mode, TransportMode::Taxi);
+    }
+}
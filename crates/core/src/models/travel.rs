@@ -0,0 +1,253 @@
+use crate::error::Error;
+use crate::models::attraction::{Attraction, Location};
+use crate::models::traffic::{TimeDependentProvider, TrafficModel};
+use crate::utils::distance::haversine_distance;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+pub const CITY_SPEED_KMH: f64 = 30.0;
+
+/// Travel time for a pair that cannot be travelled at all, e.g. no road
+/// route between them. No leg is built for such a pair.
+pub const UNREACHABLE: u32 = u32::MAX;
+
+/// Source of point-to-point travel times used by the graph, the solvers
+/// and the itinerary builders. Times are whole minutes, or `UNREACHABLE`.
+pub trait TravelTimeProvider: Send + Sync {
+    fn travel_time(&self, from: &Location, to: &Location) -> u32;
+
+    /// Travel time when leaving at `departure` (minutes from midnight).
+    /// Implementations must keep `departure + travel_time_at(..)`
+    /// non-decreasing in `departure`.
+    fn travel_time_at(&self, from: &Location, to: &Location, departure: u32) -> u32 {
+        let _ = departure;
+        self.travel_time(from, to)
+    }
+}
+
+/// Straight-line distance at a constant speed (the original model).
+#[derive(Debug, Clone, Copy)]
+pub struct HaversineProvider {
+    pub speed_kmh: f64,
+}
+
+impl Default for HaversineProvider {
+    fn default() -> Self {
+        Self { speed_kmh: CITY_SPEED_KMH }
+    }
+}
+
+impl TravelTimeProvider for HaversineProvider {
+    fn travel_time(&self, from: &Location, to: &Location) -> u32 {
+        let distance_km = haversine_distance(from.lat, from.lng, to.lat, to.lng);
+        ((distance_km / self.speed_kmh) * 60.0).ceil() as u32
+    }
+}
+
+/// Precomputed travel times between known locations, e.g. produced offline
+/// from a road network. `null` entries are `UNREACHABLE`; pairs involving a
+/// location outside the matrix (e.g. the hotel) fall back to
+/// `HaversineProvider`.
+#[derive(Debug, Clone)]
+pub struct MatrixProvider {
+    index: HashMap<(i64, i64), usize>,
+    minutes: Vec<Vec<Option<u32>>>,
+    fallback: HaversineProvider,
+}
+
+/// On-disk matrix format: `minutes[i][j]` is the time from `locations[i]`
+/// to `locations[j]`.
+#[derive(Debug, Deserialize)]
+struct MatrixFile {
+    locations: Vec<Location>,
+    minutes: Vec<Vec<Option<u32>>>,
+}
+
+/// Subset of an OSRM `/table` response. Durations are in seconds and rows
+/// follow the order of the coordinates in the request.
+#[derive(Debug, Deserialize)]
+struct OsrmTable {
+    #[serde(default)]
+    code: Option<String>,
+    durations: Vec<Vec<Option<f64>>>,
+}
+
+/// Quantize coordinates to ~0.1 m so lookups tolerate float round-trips.
+fn location_key(location: &Location) -> (i64, i64) {
+    (
+        (location.lat * 1e6).round() as i64,
+        (location.lng * 1e6).round() as i64,
+    )
+}
+
+impl MatrixProvider {
+    /// Square matrix over `locations`.
+    pub fn new(locations: &[Location], minutes: Vec<Vec<Option<u32>>>) -> Result<Self, String> {
+        if minutes.len() != locations.len() || minutes.iter().any(|row| row.len() != locations.len()) {
+            return Err(format!(
+                "Travel matrix must be {n}x{n} to match its locations",
+                n = locations.len()
+            ));
+        }
+
+        let index: HashMap<(i64, i64), usize> = locations
+            .iter()
+            .enumerate()
+            .map(|(i, loc)| (location_key(loc), i))
+            .collect();
+
+        Ok(Self {
+            index,
+            minutes,
+            fallback: HaversineProvider::default(),
+        })
+    }
+
+    /// Parse an OSRM `/table` response whose request coordinates were `points`.
+    pub fn from_osrm_table(json: &str, points: &[Location]) -> Result<Self, String> {
+        let table: OsrmTable = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid OSRM table: {}", e))?;
+
+        if let Some(code) = &table.code {
+            if code != "Ok" {
+                return Err(format!("OSRM table returned code {}", code));
+            }
+        }
+
+        let minutes = table
+            .durations
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|secs| secs.map(|s| (s / 60.0).ceil() as u32))
+                    .collect()
+            })
+            .collect();
+
+        Self::new(points, minutes)
+    }
+
+    /// Load either the native matrix format or an OSRM table. OSRM output
+    /// does not carry the original coordinates, so `points` supplies them.
+    pub fn from_json_file(path: &Path, points: &[Location]) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|e| Error::read(path, e))?;
+
+        let value: serde_json::Value = serde_json::from_str(&content).map_err(|e| Error::parse(path, e))?;
+
+        if value.get("durations").is_some() {
+            return Self::from_osrm_table(&content, points).map_err(|e| Error::dataset(path, e));
+        }
+
+        let file: MatrixFile = serde_json::from_str(&content).map_err(|e| Error::parse(path, e))?;
+        Self::new(&file.locations, file.minutes).map_err(|e| Error::dataset(path, e))
+    }
+
+    pub fn with_fallback(mut self, fallback: HaversineProvider) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+}
+
+/// Travel model for the dataset `name` under `data_dir`: its travel matrix
+/// (`matrices/<name>.json`) and traffic profile (`traffic/<name>.json`)
+/// when present, otherwise straight-line estimates.
+pub fn load_travel_model(
+    data_dir: &Path,
+    name: &str,
+    attractions: &[Attraction],
+) -> Result<Box<dyn TravelTimeProvider>, Error> {
+    let matrix_path = data_dir.join("matrices").join(format!("{}.json", name));
+    let mut travel: Box<dyn TravelTimeProvider> = if matrix_path.exists() {
+        let points: Vec<Location> = attractions.iter().map(|a| a.location).collect();
+        Box::new(MatrixProvider::from_json_file(&matrix_path, &points)?)
+    } else {
+        Box::new(HaversineProvider::default())
+    };
+
+    let traffic_path = data_dir.join("traffic").join(format!("{}.json", name));
+    if traffic_path.exists() {
+        let traffic = TrafficModel::from_json_file(&traffic_path)?;
+        travel = Box::new(TimeDependentProvider::new(travel, traffic));
+    }
+    Ok(travel)
+}
+
+impl TravelTimeProvider for MatrixProvider {
+    fn travel_time(&self, from: &Location, to: &Location) -> u32 {
+        let from_key = location_key(from);
+        let to_key = location_key(to);
+        if from_key == to_key {
+            return 0;
+        }
+
+        match self.index.get(&from_key).zip(self.index.get(&to_key)) {
+            Some((&i, &j)) => self.minutes[i][j].unwrap_or(UNREACHABLE),
+            None => self.fallback.travel_time(from, to),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loc(lat: f64, lng: f64) -> Location {
+        Location { lat, lng }
+    }
+
+    #[test]
+    fn test_haversine_matches_city_speed() {
+        let provider = HaversineProvider::default();
+        // ~1.1 km north at 30 km/h is just over 2 minutes
+        assert_eq!(provider.travel_time(&loc(13.75, 100.5), &loc(13.76, 100.5)), 3);
+        assert_eq!(provider.travel_time(&loc(13.75, 100.5), &loc(13.75, 100.5)), 0);
+    }
+
+    #[test]
+    fn test_matrix_lookup_and_fallback() {
+        let points = [loc(13.75, 100.50), loc(13.76, 100.51)];
+        let matrix = MatrixProvider::new(&points, vec![
+            vec![Some(0), Some(25)],
+            vec![Some(40), Some(0)],
+        ]).unwrap();
+
+        assert_eq!(matrix.travel_time(&points[0], &points[1]), 25);
+        assert_eq!(matrix.travel_time(&points[1], &points[0]), 40);
+
+        let hotel = loc(13.70, 100.45);
+        assert_eq!(
+            matrix.travel_time(&hotel, &points[0]),
+            HaversineProvider::default().travel_time(&hotel, &points[0])
+        );
+    }
+
+    #[test]
+    fn test_matrix_rejects_wrong_shape() {
+        let points = [loc(13.75, 100.50), loc(13.76, 100.51)];
+        assert!(MatrixProvider::new(&points, vec![vec![Some(0)]]).is_err());
+    }
+
+    #[test]
+    fn test_osrm_table_seconds_to_minutes() {
+        let points = [loc(13.75, 100.50), loc(13.76, 100.51)];
+        let json = r#"{"code":"Ok","durations":[[0.0,610.5],[null,0.0]]}"#;
+        let matrix = MatrixProvider::from_osrm_table(json, &points).unwrap();
+
+        assert_eq!(matrix.travel_time(&points[0], &points[1]), 11);
+        // No road route, so no leg either
+        assert_eq!(matrix.travel_time(&points[1], &points[0]), UNREACHABLE);
+        let taxi = &crate::models::transport::TransportOptions::default().modes[0];
+        assert!(taxi.leg(&points[1], &points[0], None, &matrix).is_none());
+        assert!(taxi.leg(&points[1], &points[0], Some(600), &matrix).is_none());
+    }
+}
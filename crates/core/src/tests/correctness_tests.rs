@@ -1,10 +1,17 @@
 //! Comprehensive correctness tests for algorithm validation
 
+use crate::algorithms::solver::SolverRegistry;
 use crate::algorithms::{greedy, simulated_annealing};
-use crate::models::attraction::{Attraction, Category, Location};
-use crate::models::constraints::SolveParams;
-use crate::utils::verifier::verify_itinerary;
-use std::collections::HashSet;
+use crate::error::Error;
+use crate::models::attraction::{Attraction, Category, Location, TimeWindow};
+use crate::models::constraints::{Appointment, DayOverride, SolveParams};
+use crate::models::itinerary::Itinerary;
+use crate::models::traffic::{TimeDependentProvider, TrafficModel};
+use crate::models::transport::TransportOptions;
+use crate::models::travel::HaversineProvider;
+use crate::utils::verifier::{find_violations, verify_itinerary, verify_opening_hours, ViolationKind};
+use chrono::{NaiveDate, Weekday};
+use std::collections::{HashMap, HashSet};
 
 fn create_test_attractions() -> Vec<Attraction> {
     vec![
@@ -12,56 +19,66 @@ fn create_test_attractions() -> Vec<Attraction> {
             id: 1,
             name: "Museum A".to_string(),
             location: Location { lat: 13.7563, lng: 100.5018 },
-            open_time: 540,  // 9:00
-            close_time: 1020, // 17:00
+            windows: vec![TimeWindow::new(540, 1020)],  // 9:00-17:00
             duration: 90,
             fee: 10.0,
             preference: 0.9,
             category: Category::Museum,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
         },
         Attraction {
             id: 2,
             name: "Restaurant B".to_string(),
             location: Location { lat: 13.7600, lng: 100.5050 },
-            open_time: 660,  // 11:00
-            close_time: 1320, // 22:00
+            windows: vec![TimeWindow::new(660, 1320)],  // 11:00-22:00
             duration: 60,
             fee: 15.0,
             preference: 0.8,
             category: Category::Restaurant,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
         },
         Attraction {
             id: 3,
             name: "Park C".to_string(),
             location: Location { lat: 13.7650, lng: 100.5100 },
-            open_time: 360,  // 6:00
-            close_time: 1080, // 18:00
+            windows: vec![TimeWindow::new(360, 1080)],  // 6:00-18:00
             duration: 120,
             fee: 0.0,
             preference: 0.7,
             category: Category::Park,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
         },
         Attraction {
             id: 4,
             name: "Landmark D".to_string(),
             location: Location { lat: 13.7700, lng: 100.5150 },
-            open_time: 480,  // 8:00
-            close_time: 1140, // 19:00
+            windows: vec![TimeWindow::new(480, 1140)],  // 8:00-19:00
             duration: 45,
             fee: 5.0,
             preference: 0.85,
             category: Category::Landmark,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
         },
         Attraction {
             id: 5,
             name: "Shopping E".to_string(),
             location: Location { lat: 13.7550, lng: 100.5080 },
-            open_time: 600,  // 10:00
-            close_time: 1260, // 21:00
+            windows: vec![TimeWindow::new(600, 1260)],  // 10:00-21:00
             duration: 150,
             fee: 20.0,
             preference: 0.75,
             category: Category::Shopping,
+            weekday_windows: HashMap::new(),
+            holiday_windows: None,
+            closed_dates: Vec::new(),
         },
     ]
 }
@@ -74,6 +91,7 @@ fn default_params() -> SolveParams {
         start_time: 540,
         hotel_lat: 13.7563,
         hotel_lng: 100.5018,
+        ..Default::default()
     }
 }
 
@@ -123,18 +141,12 @@ fn test_greedy_respects_time_windows() {
         for visit in &day.visits {
             let attr = attractions.iter().find(|a| a.id == visit.attraction_id).unwrap();
             assert!(
-                visit.arrival_time >= attr.open_time,
-                "Visit to {} arrives before opening: {} < {}",
+                attr.fits_window(visit.arrival_time, visit.departure_time),
+                "Visit to {} at {}-{} is outside opening windows {:?}",
                 visit.attraction_name,
                 visit.arrival_time,
-                attr.open_time
-            );
-            assert!(
-                visit.departure_time <= attr.close_time,
-                "Visit to {} departs after closing: {} > {}",
-                visit.attraction_name,
                 visit.departure_time,
-                attr.close_time
+                attr.windows
             );
         }
     }
@@ -150,18 +162,12 @@ fn test_sa_respects_time_windows() {
         for visit in &day.visits {
             let attr = attractions.iter().find(|a| a.id == visit.attraction_id).unwrap();
             assert!(
-                visit.arrival_time >= attr.open_time,
-                "Visit to {} arrives before opening: {} < {}",
+                attr.fits_window(visit.arrival_time, visit.departure_time),
+                "Visit to {} at {}-{} is outside opening windows {:?}",
                 visit.attraction_name,
                 visit.arrival_time,
-                attr.open_time
-            );
-            assert!(
-                visit.departure_time <= attr.close_time,
-                "Visit to {} departs after closing: {} > {}",
-                visit.attraction_name,
                 visit.departure_time,
-                attr.close_time
+                attr.windows
             );
         }
     }
@@ -462,7 +468,7 @@ fn test_cost_calculation_correct() {
     let mut expected_cost = 0.0;
     for day in &itinerary.days {
         for visit in &day.visits {
-            expected_cost += visit.fee;
+            expected_cost += visit.fee + visit.leg.fare;
         }
     }
     
@@ -473,3 +479,377 @@ fn test_cost_calculation_correct() {
         expected_cost
     );
 }
+
+#[test]
+fn test_fares_count_against_budget() {
+    let attractions = create_test_attractions();
+    let params = SolveParams {
+        total_budget: 30.0,
+        transport: TransportOptions::bangkok(),
+        ..default_params()
+    };
+
+    for itinerary in [
+        greedy::solve(&attractions, &params, 42),
+        simulated_annealing::solve(&attractions, &params, 42),
+    ] {
+        let fees: f64 = itinerary.days.iter().flat_map(|d| &d.visits).map(|v| v.fee).sum();
+        let fares: f64 = itinerary.days.iter().map(|d| d.total_fare).sum();
+        assert!((itinerary.total_cost - (fees + fares)).abs() < 0.001);
+        assert!(itinerary.total_cost <= params.total_budget);
+        assert!(verify_itinerary(&itinerary, &params).is_ok());
+    }
+}
+
+#[test]
+fn test_rush_hour_legs_pass_verifier() {
+    let attractions = create_test_attractions();
+    let params = SolveParams {
+        start_time: 420,  // 07:00, inside the morning rush
+        ..default_params()
+    };
+    let travel = TimeDependentProvider::new(
+        Box::new(HaversineProvider::default()),
+        TrafficModel::bangkok(),
+    );
+
+    for itinerary in [
+        greedy::solve_with_travel(&attractions, &params, 42, &travel),
+        simulated_annealing::solve_with_travel(&attractions, &params, 42, &travel),
+    ] {
+        let result = verify_itinerary(&itinerary, &params);
+        assert!(result.is_ok(), "Verifier failed: {:?}", result.err());
+    }
+}
+
+#[test]
+fn test_lunch_closure_is_waited_out() {
+    let temple = Attraction {
+        id: 6,
+        name: "Temple F".to_string(),
+        location: Location { lat: 13.7563, lng: 100.5018 },
+        windows: vec![TimeWindow::new(480, 720), TimeWindow::new(780, 1020)],  // lunch 12:00-13:00
+        duration: 60,
+        fee: 0.0,
+        preference: 1.0,
+        category: Category::Landmark,
+        weekday_windows: HashMap::new(),
+        holiday_windows: None,
+        closed_dates: Vec::new(),
+    };
+    let params = SolveParams {
+        num_days: 1,
+        start_time: 690,  // 11:30, too late for a full morning visit
+        ..default_params()
+    };
+
+    for itinerary in [
+        greedy::solve(std::slice::from_ref(&temple), &params, 42),
+        simulated_annealing::solve(std::slice::from_ref(&temple), &params, 42),
+    ] {
+        assert_eq!(itinerary.total_attractions, 1);
+        let visit = &itinerary.days[0].visits[0];
+        assert_eq!(visit.arrival_time, 780);
+        assert!(verify_itinerary(&itinerary, &params).is_ok());
+    }
+}
+
+#[test]
+fn test_closed_weekdays_and_holidays_are_skipped() {
+    let mut attractions = create_test_attractions();
+    // Museum A closes on Mondays, Landmark D on public holidays
+    attractions[0].weekday_windows.insert(Weekday::Mon, Vec::new());
+    attractions[3].holiday_windows = Some(Vec::new());
+
+    let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+    let params = SolveParams {
+        num_days: 1,
+        start_date: Some(monday),
+        holidays: vec![monday],
+        ..default_params()
+    };
+
+    for itinerary in [
+        greedy::solve(&attractions, &params, 42),
+        simulated_annealing::solve(&attractions, &params, 42),
+    ] {
+        for visit in &itinerary.days[0].visits {
+            assert!(visit.attraction_id != 1 && visit.attraction_id != 4);
+        }
+        let result = verify_opening_hours(&itinerary, &attractions, &params);
+        assert!(result.is_ok(), "Verifier failed: {:?}", result.err());
+    }
+
+    // The same plan on an undated trip would visit Museum A
+    let undated = greedy::solve(&attractions, &SolveParams { start_date: None, ..params.clone() }, 42);
+    assert!(verify_opening_hours(&undated, &attractions, &params).is_err());
+}
+
+#[test]
+fn test_solvers_pass_full_verifier() {
+    let attractions = create_test_attractions();
+    let params = default_params();
+    let travel = HaversineProvider::default();
+
+    for solver in SolverRegistry::default().iter() {
+        let itinerary = solver.solve_default(&attractions, &params, 42, &travel);
+        let violations = find_violations(&itinerary, &attractions, &params, &travel);
+        assert!(violations.is_empty(), "{} failed: {:?}", solver.name(), violations);
+    }
+}
+
+#[test]
+fn test_full_verifier_reports_every_violation() {
+    let attractions = create_test_attractions();
+    let params = default_params();
+    let travel = HaversineProvider::default();
+    let plan = greedy::solve(&attractions, &params, 42);
+    assert!(plan.days[0].visits.len() >= 2);
+    let kinds = |itinerary: &Itinerary, attractions: &[Attraction], params: &SolveParams| -> Vec<ViolationKind> {
+        find_violations(itinerary, attractions, params, &travel).into_iter().map(|v| v.kind).collect()
+    };
+    assert!(kinds(&plan, &attractions, &params).is_empty());
+
+    let mut itinerary = plan.clone();
+    itinerary.days[0].visits[0].fee += 5.0;
+    itinerary.days[0].visits[1].attraction_id = 99;
+    let found = kinds(&itinerary, &attractions, &params);
+    assert!(found.contains(&ViolationKind::CatalogueMismatch));
+    assert!(found.contains(&ViolationKind::UnknownAttraction));
+    assert!(found.contains(&ViolationKind::TotalsMismatch));
+
+    let mut itinerary = plan.clone();
+    let repeat = itinerary.days[0].visits[0].clone();
+    itinerary.days[0].visits.push(repeat);
+    assert!(kinds(&itinerary, &attractions, &params).contains(&ViolationKind::DuplicateVisit));
+
+    let mut itinerary = plan.clone();
+    let visit = &mut itinerary.days[0].visits[0];
+    visit.departure_time = visit.arrival_time + 10;
+    assert!(kinds(&itinerary, &attractions, &params).contains(&ViolationKind::ShortVisit));
+
+    // Pushed back so the visit runs past closing time
+    let mut itinerary = plan.clone();
+    let visit = &mut itinerary.days[0].visits[0];
+    let attr = attractions.iter().find(|a| a.id == visit.attraction_id).unwrap();
+    visit.arrival_time = attr.windows[0].close - 10;
+    visit.departure_time = visit.arrival_time + attr.duration;
+    assert!(kinds(&itinerary, &attractions, &params).contains(&ViolationKind::OutsideOpeningHours));
+
+    let mut itinerary = plan.clone();
+    itinerary.days[0].visits[1].wait_minutes += 5;
+    assert!(kinds(&itinerary, &attractions, &params).contains(&ViolationKind::TimingMismatch));
+
+    // The same plan on a Monday, when its first attraction is closed
+    let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+    let dated = SolveParams { start_date: Some(monday), ..params.clone() };
+    let mut closed_monday = attractions.clone();
+    let first = plan.days[0].visits[0].attraction_id;
+    closed_monday.iter_mut().find(|a| a.id == first).unwrap().weekday_windows.insert(Weekday::Mon, Vec::new());
+    assert!(kinds(&plan, &closed_monday, &dated).contains(&ViolationKind::Closed));
+
+    // An open-ended day cut short, so its last visit runs past its end
+    let last = plan.days[0].visits.last().unwrap().departure_time;
+    let short = SolveParams {
+        daily_time_budget: last - params.start_time - 1,
+        day_overrides: vec![DayOverride { day: 1, open_ended: true, ..Default::default() }],
+        ..params.clone()
+    };
+    assert_eq!(kinds(&plan, &attractions, &short), vec![ViolationKind::OverTime]);
+
+    assert!(plan.total_cost > 1.0);
+    let tight = SolveParams { total_budget: plan.total_cost - 1.0, ..params.clone() };
+    assert!(kinds(&plan, &attractions, &tight).contains(&ViolationKind::OverBudget));
+}
+
+#[test]
+fn test_solvers_reach_each_days_end_point() {
+    let attractions = create_test_attractions();
+    let travel = HaversineProvider::default();
+    // The trip moves to a second hotel on day 2 and ends at the airport
+    let params = SolveParams {
+        daily_time_budget: 360,
+        end_lat: Some(13.4500),
+        end_lng: Some(100.7501),
+        day_overrides: vec![DayOverride {
+            day: 2,
+            start_lat: Some(13.7650),
+            start_lng: Some(100.5100),
+            ..Default::default()
+        }],
+        ..default_params()
+    };
+
+    for solver in SolverRegistry::default().iter() {
+        let itinerary = solver.solve_default(&attractions, &params, 42, &travel);
+        let violations = find_violations(&itinerary, &attractions, &params, &travel);
+        assert!(violations.is_empty(), "{} failed: {:?}", solver.name(), violations);
+    }
+
+    // A plan made for returning to the hotel does not get the traveller to
+    // the airport: its return leg is far too quick for that trip
+    let one_day = SolveParams { num_days: 1, day_overrides: Vec::new(), ..params.clone() };
+    let to_hotel = SolveParams { end_lat: None, end_lng: None, ..one_day.clone() };
+    let itinerary = greedy::solve(&attractions, &to_hotel, 42);
+    let kinds: Vec<_> = find_violations(&itinerary, &attractions, &one_day, &travel)
+        .into_iter()
+        .map(|v| v.kind)
+        .collect();
+    assert_eq!(kinds, vec![ViolationKind::InfeasibleLeg]);
+}
+
+#[test]
+fn test_open_ended_day_skips_the_closing_leg() {
+    let attractions = create_test_attractions();
+    let travel = HaversineProvider::default();
+    // Far from every attraction, so returning takes most of the day
+    let params = SolveParams {
+        num_days: 1,
+        daily_time_budget: 300,
+        hotel_lat: 13.9000,
+        hotel_lng: 100.6000,
+        ..default_params()
+    };
+    let open = SolveParams {
+        day_overrides: vec![DayOverride { day: 1, open_ended: true, ..Default::default() }],
+        ..params.clone()
+    };
+
+    let closed = greedy::solve(&attractions, &params, 42);
+    let open_ended = greedy::solve(&attractions, &open, 42);
+    assert!(open_ended.total_attractions > closed.total_attractions);
+    assert!(find_violations(&open_ended, &attractions, &open, &travel).is_empty());
+    assert!(!find_violations(&open_ended, &attractions, &params, &travel).is_empty());
+
+    // Open-ended days still end when their time budget runs out
+    for solver in SolverRegistry::default().iter() {
+        let itinerary = solver.solve_default(&attractions, &open, 42, &travel);
+        let violations = find_violations(&itinerary, &attractions, &open, &travel);
+        assert!(violations.is_empty(), "{} failed: {:?}", solver.name(), violations);
+        for visit in itinerary.days.iter().flat_map(|d| &d.visits) {
+            assert!(visit.departure_time <= open.day_end(1), "{} runs past the day: {:?}", solver.name(), visit);
+        }
+    }
+}
+
+#[test]
+fn test_solvers_plan_around_per_day_settings() {
+    let attractions = create_test_attractions();
+    let travel = HaversineProvider::default();
+    // Day 1 starts after a morning flight and has a lunch booking; the
+    // trip moves to another hotel on day 2
+    let lunch = Appointment { name: "Lunch".to_string(), lat: 13.7600, lng: 100.5050, start: 720, end: 780 };
+    let params = SolveParams {
+        day_overrides: vec![
+            DayOverride { day: 1, start_time: Some(660), appointments: vec![lunch.clone()], ..Default::default() },
+            DayOverride {
+                day: 2,
+                hotel_lat: Some(13.7700),
+                hotel_lng: Some(100.5150),
+                daily_time_budget: Some(300),
+                ..Default::default()
+            },
+        ],
+        ..default_params()
+    };
+    assert_eq!(params.validate(), Ok(()));
+
+    for solver in SolverRegistry::default().iter() {
+        let itinerary = solver.solve_default(&attractions, &params, 42, &travel);
+        let violations = find_violations(&itinerary, &attractions, &params, &travel);
+        assert!(violations.is_empty(), "{} failed: {:?}", solver.name(), violations);
+        assert!(verify_itinerary(&itinerary, &params).is_ok(), "{} failed the basic verifier", solver.name());
+
+        for visit in &itinerary.days[0].visits {
+            assert!(visit.arrival_time >= 660, "{} starts before the flight lands", solver.name());
+            assert!(
+                visit.departure_time <= lunch.start || visit.arrival_time >= lunch.end,
+                "{} overlaps lunch: {:?}",
+                solver.name(),
+                visit
+            );
+        }
+        let booked = &itinerary.days[0].appointments;
+        assert_eq!(booked.len(), 1, "{} does not record lunch", solver.name());
+        assert!(booked[0].arrival_time <= lunch.start && booked[0].leg.travel_time > 0);
+    }
+
+    // A plan that ignores the booking is caught
+    let unbooked = SolveParams { day_overrides: Vec::new(), ..params.clone() };
+    let itinerary = greedy::solve(&attractions, &unbooked, 42);
+    let kinds: Vec<_> = find_violations(&itinerary, &attractions, &params, &travel)
+        .into_iter()
+        .map(|v| v.kind)
+        .collect();
+    assert!(kinds.contains(&ViolationKind::MissedAppointment), "{:?}", kinds);
+
+    // So is one that gets to lunch for free in no time
+    let mut itinerary = greedy::solve(&attractions, &params, 42);
+    let leg = &mut itinerary.days[0].appointments[0].leg;
+    leg.travel_time = 0;
+    leg.fare = 0.0;
+    let kinds: Vec<_> = find_violations(&itinerary, &attractions, &params, &travel)
+        .into_iter()
+        .map(|v| v.kind)
+        .collect();
+    for kind in [ViolationKind::InfeasibleLeg, ViolationKind::TimingMismatch, ViolationKind::TotalsMismatch] {
+        assert!(kinds.contains(&kind), "{:?} not in {:?}", kind, kinds);
+    }
+    assert!(verify_itinerary(&itinerary, &params).is_err());
+}
+
+#[test]
+fn test_return_leg_is_part_of_each_day() {
+    let attractions = create_test_attractions();
+    let travel = HaversineProvider::default();
+    let params = SolveParams {
+        total_budget: 60.0,
+        hotel_lat: 13.7400,
+        hotel_lng: 100.5300,
+        transport: TransportOptions::bangkok(),
+        ..default_params()
+    };
+
+    for solver in SolverRegistry::default().iter() {
+        let itinerary = solver.solve_default(&attractions, &params, 42, &travel);
+        let violations = find_violations(&itinerary, &attractions, &params, &travel);
+        assert!(violations.is_empty(), "{} failed: {:?}", solver.name(), violations);
+        assert!(itinerary.total_cost <= params.total_budget + 1e-9);
+
+        for day in itinerary.days.iter().filter(|d| !d.visits.is_empty()) {
+            let leg = day.return_leg.unwrap_or_else(|| panic!("{} day {} has no return leg", solver.name(), day.day));
+            let last = day.visits.last().unwrap();
+            assert_eq!(day.return_arrival_time, Some(last.departure_time + leg.travel_time));
+            assert!(day.return_arrival_time.unwrap() <= params.day_end(day.day));
+            let to_visits: u32 = day.visits.iter().map(|v| v.leg.travel_time).sum();
+            assert_eq!(day.total_travel_time, to_visits + leg.travel_time);
+        }
+    }
+
+    // Pushing the last visit of a day back makes the way home late
+    let mut itinerary = greedy::solve(&attractions, &params, 42);
+    let day = &mut itinerary.days[0];
+    let shift = params.day_end(1) - day.return_arrival_time.unwrap() + 1;
+    let last = day.visits.last_mut().unwrap();
+    last.arrival_time += shift;
+    last.departure_time += shift;
+    last.wait_minutes += shift;
+    let kinds: Vec<_> = find_violations(&itinerary, &attractions, &params, &travel)
+        .into_iter()
+        .map(|v| v.kind)
+        .collect();
+    assert!(kinds.contains(&ViolationKind::LateReturn), "{:?}", kinds);
+
+    // The basic verifier checks the way back adds up and is there at all
+    let basic_kind = |itinerary: &Itinerary| match verify_itinerary(itinerary, &params) {
+        Err(Error::InvalidItinerary(violation)) => Some(violation.kind),
+        _ => None,
+    };
+    let mut itinerary = greedy::solve(&attractions, &params, 42);
+    assert_eq!(basic_kind(&itinerary), None);
+    *itinerary.days[0].return_arrival_time.as_mut().unwrap() -= 1;
+    assert_eq!(basic_kind(&itinerary), Some(ViolationKind::TimingMismatch));
+    itinerary.days[0].return_leg = None;
+    itinerary.days[0].return_arrival_time = None;
+    assert_eq!(basic_kind(&itinerary), Some(ViolationKind::LateReturn));
+}
@@ -1,7 +1,14 @@
 use crate::models::attraction::Attraction;
 use crate::models::itinerary::Itinerary;
 use crate::models::constraints::SolveParams;
-use crate::utils::verifier::verify_itinerary;
+use crate::models::travel::TravelTimeProvider;
+use crate::utils::parallel::map_indexed;
+use crate::utils::statistics::{wilcoxon_signed_rank, Summary, WilcoxonResult};
+use crate::utils::verifier::find_violations;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
 
 pub struct ExperimentStats {
     pub mean_satisfaction: f64,
@@ -10,34 +17,155 @@ pub struct ExperimentStats {
     pub valid_runs: usize,
     pub mean_attractions: f64,
     pub mean_cost: f64,
+    /// Mean shortfall from the optimum (or its upper bound), in percent.
+    pub optimality_gap_pct: Option<f64>,
+    /// Satisfaction over the valid runs.
+    pub satisfaction: Summary,
+    /// Every run in seed order, valid or not.
+    pub runs: Vec<RunOutcome>,
+}
+
+/// One seed's result; `error` holds the verifier's complaints for invalid
+/// itineraries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct RunOutcome {
+    pub seed: u64,
+    pub satisfaction: f64,
+    pub ms: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
+impl ExperimentStats {
+    /// Fill in the optimality gap against an upper bound on satisfaction,
+    /// e.g. from `branch_and_bound::solve_exact`.
+    pub fn with_upper_bound(mut self, upper_bound: f64) -> Self {
+        self.optimality_gap_pct = Some(if upper_bound > 0.0 {
+            ((upper_bound - self.mean_satisfaction) / upper_bound * 100.0).max(0.0)
+        } else {
+            0.0
+        });
+        self
+    }
+
+    pub fn invalid_runs(&self) -> impl Iterator<Item = &RunOutcome> {
+        self.runs.iter().filter(|run| run.error.is_some())
+    }
+
+    /// Wilcoxon signed-rank test of these runs against `baseline`'s, paired
+    /// by seed over the seeds valid in both.
+    pub fn compare(&self, baseline: &ExperimentStats) -> WilcoxonResult {
+        compare_runs(&self.runs, &baseline.runs)
+    }
+}
+
+/// Wilcoxon signed-rank test of `runs` against `baseline`, paired by seed
+/// over the seeds valid in both.
+pub fn compare_runs(runs: &[RunOutcome], baseline: &[RunOutcome]) -> WilcoxonResult {
+    let pairs: Vec<(f64, f64)> = runs
+        .iter()
+        .filter(|run| run.error.is_none())
+        .filter_map(|run| {
+            baseline
+                .iter()
+                .find(|other| other.seed == run.seed && other.error.is_none())
+                .map(|other| (run.satisfaction, other.satisfaction))
+        })
+        .collect();
+    wilcoxon_signed_rank(&pairs)
+}
+
+fn run_seed(run: usize) -> u64 {
+    (run as u64) * 13 + 7
+}
+
+/// The seeds `run_experiments` uses for `n_runs` runs.
+pub fn experiment_seeds(n_runs: usize) -> Vec<u64> {
+    (0..n_runs).map(run_seed).collect()
+}
+
+/// Run `algorithm` once per seed and check every itinerary with
+/// `find_violations` against `travel`, the model it was planned with.
 pub fn run_experiments<F>(
     attractions: &[Attraction],
     params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
     n_runs: usize,
     mut algorithm: F,
 ) -> ExperimentStats
 where
     F: FnMut(&[Attraction], &SolveParams, u64) -> Itinerary,
 {
+    let seeds = experiment_seeds(n_runs);
+    let itineraries: Vec<Itinerary> = seeds.iter().map(|&seed| algorithm(attractions, params, seed)).collect();
+    summarize(attractions, params, travel, &seeds, &itineraries)
+}
+
+/// Like `run_experiments`, spreading the runs over `threads` threads. Each
+/// run keeps its seed, so the statistics match the sequential runner apart
+/// from timings, which grow when runs compete for cores.
+pub fn run_experiments_parallel<F>(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+    n_runs: usize,
+    threads: usize,
+    algorithm: F,
+) -> ExperimentStats
+where
+    F: Fn(&[Attraction], &SolveParams, u64) -> Itinerary + Sync,
+{
+    run_experiments_on_seeds(attractions, params, travel, &experiment_seeds(n_runs), threads, algorithm)
+}
+
+/// Like `run_experiments_parallel`, with one run per seed in `seeds`.
+pub fn run_experiments_on_seeds<F>(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+    seeds: &[u64],
+    threads: usize,
+    algorithm: F,
+) -> ExperimentStats
+where
+    F: Fn(&[Attraction], &SolveParams, u64) -> Itinerary + Sync,
+{
+    let itineraries = map_indexed(seeds.len(), threads, |run| algorithm(attractions, params, seeds[run]));
+    summarize(attractions, params, travel, seeds, &itineraries)
+}
+
+fn summarize(
+    attractions: &[Attraction],
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+    seeds: &[u64],
+    itineraries: &[Itinerary],
+) -> ExperimentStats {
     let mut satisfactions = Vec::new();
     let mut times = Vec::new();
     let mut valid_count = 0;
     let mut total_attractions = 0;
     let mut total_cost = 0.0;
+    let mut runs = Vec::with_capacity(itineraries.len());
 
-    for run in 0..n_runs {
-        let seed = (run as u64) * 13 + 7;
-        let itinerary = algorithm(attractions, params, seed);
-        
-        if verify_itinerary(&itinerary, params).is_ok() {
+    for (&seed, itinerary) in seeds.iter().zip(itineraries) {
+        let violations = find_violations(itinerary, attractions, params, travel);
+        if violations.is_empty() {
             satisfactions.push(itinerary.total_satisfaction);
             times.push(itinerary.computation_ms as f64);
             total_attractions += itinerary.total_attractions;
             total_cost += itinerary.total_cost;
             valid_count += 1;
         }
+        runs.push(RunOutcome {
+            seed,
+            satisfaction: itinerary.total_satisfaction,
+            ms: itinerary.computation_ms as f64,
+            error: (!violations.is_empty()).then(|| {
+                violations.iter().map(|v| v.message.as_str()).collect::<Vec<_>>().join("; ")
+            }),
+        });
     }
 
     let mean_sat = if !satisfactions.is_empty() {
@@ -68,5 +196,97 @@ where
         valid_runs: valid_count,
         mean_attractions: if valid_count > 0 { total_attractions as f64 / valid_count as f64 } else { 0.0 },
         mean_cost: if valid_count > 0 { total_cost / valid_count as f64 } else { 0.0 },
+        optimality_gap_pct: None,
+        satisfaction: Summary::of(&satisfactions),
+        runs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::simulated_annealing;
+    use crate::models::attraction::{Category, Location, TimeWindow};
+    use crate::models::travel::HaversineProvider;
+    use std::collections::HashMap;
+
+    fn museums() -> Vec<Attraction> {
+        (0..8)
+            .map(|i| Attraction {
+                id: i + 1,
+                name: format!("Attraction {}", i + 1),
+                location: Location { lat: 13.75 + 0.005 * i as f64, lng: 100.50 },
+                windows: vec![TimeWindow::new(540, 1020)],
+                duration: 60,
+                fee: 10.0,
+                preference: 0.3 + 0.08 * i as f64,
+                category: Category::Museum,
+                weekday_windows: HashMap::new(),
+                holiday_windows: None,
+                closed_dates: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parallel_runs_match_sequential() {
+        let attractions = museums();
+        let params = SolveParams::default();
+        let travel = HaversineProvider::default();
+
+        let sequential = run_experiments(&attractions, &params, &travel, 6, simulated_annealing::solve);
+        for threads in [1, 3, 8] {
+            let parallel =
+                run_experiments_parallel(&attractions, &params, &travel, 6, threads, simulated_annealing::solve);
+            assert_eq!(parallel.valid_runs, sequential.valid_runs);
+            assert_eq!(parallel.mean_satisfaction, sequential.mean_satisfaction);
+            assert_eq!(parallel.std_satisfaction, sequential.std_satisfaction);
+            assert_eq!(parallel.satisfaction, sequential.satisfaction);
+        }
+
+        let summary = &sequential.satisfaction;
+        assert!(summary.min <= summary.median && summary.median <= summary.max);
+        assert_eq!(sequential.runs.len(), 6);
+        assert_eq!(sequential.invalid_runs().count(), 6 - sequential.valid_runs);
+        // Against itself every difference is zero
+        assert_eq!(sequential.compare(&sequential).p_value, 1.0);
+    }
+
+    #[test]
+    fn test_invalid_runs_keep_their_errors() {
+        let attractions = Vec::new();
+        let params = SolveParams::default();
+        let stats = run_experiments(&attractions, &params, &HaversineProvider::default(), 3, |_, _, seed| {
+            let mut itinerary = Itinerary::empty("broken".to_string());
+            itinerary.total_cost = if seed == 7 { params.total_budget + 1.0 } else { 0.0 };
+            itinerary
+        });
+
+        assert_eq!(stats.valid_runs, 2);
+        let invalid: Vec<_> = stats.invalid_runs().collect();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].seed, 7);
+        assert!(!invalid[0].error.as_ref().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_made_up_legs_are_invalid() {
+        let attractions = museums();
+        let params = SolveParams::default();
+        // Every leg is recorded as instant, with the time moved into waiting
+        // so the timings still add up
+        let stats = run_experiments(&attractions, &params, &HaversineProvider::default(), 2, |attrs, params, seed| {
+            let mut itinerary = simulated_annealing::solve(attrs, params, seed);
+            for day in &mut itinerary.days {
+                for visit in &mut day.visits {
+                    day.total_travel_time -= visit.leg.travel_time;
+                    visit.wait_minutes += visit.leg.travel_time;
+                    visit.leg.travel_time = 0;
+                }
+            }
+            itinerary
+        });
+        assert_eq!(stats.valid_runs, 0);
+        assert!(stats.runs.iter().all(|run| run.error.as_ref().unwrap().contains("needs at least")));
     }
 }
@@ -1,7 +1,14 @@
 pub mod distance;
 pub mod verifier;
 pub mod experiments;
+pub mod parallel;
+pub mod statistics;
 
 pub use distance::haversine_distance;
-pub use verifier::verify_itinerary;
-pub use experiments::run_experiments;
+pub use verifier::{find_violations, verify_itinerary, verify_opening_hours, Violation, ViolationKind};
+pub use experiments::{
+    compare_runs, experiment_seeds, run_experiments, run_experiments_on_seeds, run_experiments_parallel, ExperimentStats,
+    RunOutcome,
+};
+pub use parallel::{default_threads, map_indexed};
+pub use statistics::{wilcoxon_signed_rank, Summary, WilcoxonResult};
@@ -0,0 +1,59 @@
+//! Fan independent runs out over threads without changing their results.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Threads to use when the caller has no preference.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// `(0..n).map(f)` on up to `threads` threads. Results come back in index
+/// order, so they do not depend on the thread count as long as `f(i)` only
+/// depends on `i`.
+pub fn map_indexed<R, F>(n: usize, threads: usize, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(usize) -> R + Sync,
+{
+    let threads = threads.clamp(1, n.max(1));
+    if threads == 1 {
+        return (0..n).map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..n).map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= n {
+                    break;
+                }
+                let result = f(i);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every index is mapped"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_keep_index_order() {
+        let square = |i: usize| i * i;
+        let sequential = map_indexed(50, 1, square);
+        assert_eq!(map_indexed(50, 7, square), sequential);
+        assert_eq!(map_indexed(3, 64, square), vec![0, 1, 4]);
+        assert!(map_indexed(0, 4, square).is_empty());
+    }
+}
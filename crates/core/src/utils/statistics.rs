@@ -0,0 +1,214 @@
+//! Descriptive statistics and significance tests for experiment results.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+const BOOTSTRAP_RESAMPLES: usize = 2_000;
+/// Above this many non-zero differences the Wilcoxon test uses the normal
+/// approximation instead of the exact distribution.
+const WILCOXON_EXACT_MAX: usize = 50;
+
+/// Five-number summary of a sample plus a 95% confidence interval for its
+/// mean. All zero for an empty sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Summary {
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    /// Percentile bootstrap interval for the mean.
+    pub ci95_low: f64,
+    pub ci95_high: f64,
+}
+
+impl Summary {
+    pub fn of(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let (ci95_low, ci95_high) = bootstrap_mean_ci(samples, 0.95);
+        Self {
+            min: sorted[0],
+            q1: quantile(&sorted, 0.25),
+            median: quantile(&sorted, 0.5),
+            q3: quantile(&sorted, 0.75),
+            max: sorted[sorted.len() - 1],
+            ci95_low,
+            ci95_high,
+        }
+    }
+}
+
+pub fn mean(samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().sum::<f64>() / samples.len() as f64
+    }
+}
+
+/// The `q`th quantile of an ascending, non-empty sample, interpolating
+/// linearly between order statistics.
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let below = position.floor() as usize;
+    let above = position.ceil() as usize;
+    sorted[below] + (sorted[above] - sorted[below]) * (position - below as f64)
+}
+
+/// Percentile bootstrap confidence interval for the mean. Resampling uses a
+/// fixed seed, so the interval is reproducible.
+pub fn bootstrap_mean_ci(samples: &[f64], confidence: f64) -> (f64, f64) {
+    if samples.len() < 2 {
+        let m = mean(samples);
+        return (m, m);
+    }
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut means: Vec<f64> = (0..BOOTSTRAP_RESAMPLES)
+        .map(|_| {
+            let total: f64 = (0..samples.len()).map(|_| samples[rng.gen_range(0..samples.len())]).sum();
+            total / samples.len() as f64
+        })
+        .collect();
+    means.sort_by(f64::total_cmp);
+    let tail = (1.0 - confidence) / 2.0;
+    (quantile(&means, tail), quantile(&means, 1.0 - tail))
+}
+
+/// Outcome of a Wilcoxon signed-rank test on paired samples.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct WilcoxonResult {
+    /// Pairs with a non-zero difference; ties are dropped.
+    pub n: usize,
+    /// Rank sum of the pairs where the first sample is larger.
+    pub w_plus: f64,
+    pub w_minus: f64,
+    /// Two-sided p-value for "the differences are symmetric around zero".
+    pub p_value: f64,
+    /// Whether `p_value` is exact or from the normal approximation.
+    pub exact: bool,
+}
+
+/// Wilcoxon signed-rank test on `(a, b)` pairs, e.g. two solvers' results
+/// on the same seeds. Tied absolute differences share their mean rank.
+pub fn wilcoxon_signed_rank(pairs: &[(f64, f64)]) -> WilcoxonResult {
+    let mut diffs: Vec<f64> = pairs.iter().map(|(a, b)| a - b).filter(|d| *d != 0.0).collect();
+    diffs.sort_by(|x, y| x.abs().total_cmp(&y.abs()));
+    let n = diffs.len();
+
+    // Mean ranks, doubled so that ties stay integral
+    let mut doubled_ranks = vec![0usize; n];
+    let mut tie_sizes = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && diffs[j + 1].abs() == diffs[i].abs() {
+            j += 1;
+        }
+        for rank in &mut doubled_ranks[i..=j] {
+            *rank = i + j + 2;
+        }
+        tie_sizes.push((j - i + 1) as f64);
+        i = j + 1;
+    }
+
+    let w_plus: f64 = diffs.iter().zip(&doubled_ranks).filter(|(d, _)| **d > 0.0).map(|(_, r)| *r as f64 / 2.0).sum();
+    let total = (n * (n + 1)) as f64 / 2.0;
+    let w_minus = total - w_plus;
+    let statistic = w_plus.min(w_minus);
+
+    if n == 0 {
+        return WilcoxonResult { n, w_plus, w_minus, p_value: 1.0, exact: true };
+    }
+
+    let (p_value, exact) = if n <= WILCOXON_EXACT_MAX {
+        // Number of sign assignments giving each doubled rank sum
+        let max_sum: usize = doubled_ranks.iter().sum();
+        let mut counts = vec![0.0f64; max_sum + 1];
+        counts[0] = 1.0;
+        for &rank in &doubled_ranks {
+            for sum in (rank..=max_sum).rev() {
+                counts[sum] += counts[sum - rank];
+            }
+        }
+        let limit = (statistic * 2.0).round() as usize;
+        let tail: f64 = counts[..=limit].iter().sum::<f64>() / 2f64.powi(n as i32);
+        ((2.0 * tail).min(1.0), true)
+    } else {
+        let mean = total / 2.0;
+        let ties: f64 = tie_sizes.iter().map(|t| t * t * t - t).sum();
+        let variance = (n * (n + 1) * (2 * n + 1)) as f64 / 24.0 - ties / 48.0;
+        let z = (statistic - mean + 0.5) / variance.sqrt();
+        ((2.0 * normal_cdf(z)).min(1.0), false)
+    };
+
+    WilcoxonResult { n, w_plus, w_minus, p_value, exact }
+}
+
+/// Standard normal CDF (Abramowitz and Stegun 7.1.26, error below 1.5e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_quantiles_and_ci() {
+        let summary = Summary::of(&[5.0, 1.0, 4.0, 2.0, 3.0]);
+        assert_eq!((summary.min, summary.q1, summary.median, summary.q3, summary.max), (1.0, 2.0, 3.0, 4.0, 5.0));
+        assert!(summary.ci95_low < 3.0 && 3.0 < summary.ci95_high);
+        assert!(summary.ci95_low >= 1.0 && summary.ci95_high <= 5.0);
+        assert_eq!(summary, Summary::of(&[5.0, 1.0, 4.0, 2.0, 3.0]));
+
+        assert_eq!(quantile(&[1.0, 2.0], 0.5), 1.5);
+        assert_eq!(Summary::of(&[]), Summary::default());
+        let single = Summary::of(&[7.0]);
+        assert_eq!((single.median, single.ci95_low, single.ci95_high), (7.0, 7.0, 7.0));
+    }
+
+    #[test]
+    fn test_wilcoxon_exact_and_approximate() {
+        // All eight differences positive: p = 2 / 2^8
+        let pairs: Vec<(f64, f64)> = (1..=8).map(|d| (10.0 + d as f64, 10.0)).collect();
+        let result = wilcoxon_signed_rank(&pairs);
+        assert_eq!((result.n, result.w_plus, result.w_minus), (8, 36.0, 0.0));
+        assert!(result.exact);
+        assert!((result.p_value - 2.0 / 256.0).abs() < 1e-12);
+
+        // Identical samples carry no evidence either way
+        let same = wilcoxon_signed_rank(&[(1.0, 1.0), (2.0, 2.0)]);
+        assert_eq!((same.n, same.p_value), (0, 1.0));
+
+        // Balanced signs with tied magnitudes
+        let balanced = wilcoxon_signed_rank(&[(1.0, 0.0), (0.0, 1.0), (2.0, 0.0), (0.0, 2.0)]);
+        assert_eq!(balanced.w_plus, balanced.w_minus);
+        assert_eq!(balanced.p_value, 1.0);
+
+        // Larger samples use the normal approximation
+        let pairs: Vec<(f64, f64)> = (1..=60).map(|d| (if d % 5 == 0 { -d } else { d } as f64, 0.0)).collect();
+        let approx = wilcoxon_signed_rank(&pairs);
+        assert!(!approx.exact);
+        assert!(approx.p_value < 0.01);
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-6);
+        assert!((normal_cdf(-1.96) - 0.025).abs() < 1e-4);
+    }
+}
@@ -1,17 +1,31 @@
-use crate::models::itinerary::Itinerary;
-use crate::models::constraints::SolveParams;
+use crate::error::Error;
+use crate::models::attraction::{Attraction, Location};
+use crate::models::itinerary::{AppointmentVisit, Itinerary};
+use crate::models::constraints::{Appointment, SolveParams};
+use crate::models::transport::Leg;
+use crate::models::travel::TravelTimeProvider;
+use serde::Serialize;
 use std::collections::HashSet;
 
-pub fn verify_itinerary(itinerary: &Itinerary, params: &SolveParams) -> Result<(), String> {
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+
+/// Check an itinerary on its own: no repeat visits, consistent timings and
+/// the time and money budgets. Stops at the first violation. Recorded legs
+/// are taken at face value; `find_violations` checks them against the
+/// travel model.
+pub fn verify_itinerary(itinerary: &Itinerary, params: &SolveParams) -> Result<(), Error> {
     let mut seen_ids = HashSet::new();
 
     // Check no duplicates across all days
     for day in &itinerary.days {
         for visit in &day.visits {
             if !seen_ids.insert(visit.attraction_id) {
-                return Err(format!(
-                    "Duplicate visit to attraction {} on day {}",
-                    visit.attraction_id, day.day
+                return Err(violation(
+                    ViolationKind::DuplicateVisit,
+                    Some(day.day),
+                    Some(visit.attraction_id),
+                    format!("Duplicate visit to attraction {} on day {}", visit.attraction_id, day.day),
                 ));
             }
         }
@@ -19,15 +33,24 @@ pub fn verify_itinerary(itinerary: &Itinerary, params: &SolveParams) -> Result<(
 
     // Check each day
     for day in &itinerary.days {
+        let mut left_at = params.day_start_time(day.day);
+        let mut appointments = day.appointments.iter().peekable();
+
         // Check chronological order and time windows
         for i in 0..day.visits.len() {
             let visit = &day.visits[i];
+            let (d, id) = (Some(day.day), Some(visit.attraction_id));
             
             // Time window check (we don't have attraction data here, assume arrival/departure are valid)
             if visit.arrival_time > visit.departure_time {
-                return Err(format!(
-                    "Invalid time: arrival {} > departure {} for attraction {}",
-                    visit.arrival_time, visit.departure_time, visit.attraction_id
+                return Err(violation(
+                    ViolationKind::Overlap,
+                    d,
+                    id,
+                    format!(
+                        "Invalid time: arrival {} > departure {} for attraction {}",
+                        visit.arrival_time, visit.departure_time, visit.attraction_id
+                    ),
                 ));
             }
 
@@ -35,36 +58,678 @@ pub fn verify_itinerary(itinerary: &Itinerary, params: &SolveParams) -> Result<(
             if i > 0 {
                 let prev = &day.visits[i - 1];
                 if visit.arrival_time < prev.departure_time {
-                    return Err(format!(
-                        "Overlapping visits on day {}: attraction {} starts before {} ends",
-                        day.day, visit.attraction_id, prev.attraction_id
+                    return Err(violation(
+                        ViolationKind::Overlap,
+                        d,
+                        id,
+                        format!(
+                            "Overlapping visits on day {}: attraction {} starts before {} ends",
+                            day.day, visit.attraction_id, prev.attraction_id
+                        ),
                     ));
                 }
             }
+
+            // Check the recorded leg and wait account for the gap since the
+            // previous stop, or since an appointment in between
+            while let Some(appointment) = appointments.next_if(|a| a.start < visit.departure_time) {
+                check_appointment_timing(day.day, appointment, left_at)?;
+                left_at = appointment.end;
+            }
+            let expected = left_at + visit.leg.travel_time + visit.wait_minutes;
+            if visit.arrival_time != expected {
+                return Err(violation(
+                    ViolationKind::TimingMismatch,
+                    d,
+                    id,
+                    format!(
+                        "Day {}: attraction {} starts at {} but leaving at {} with {} min of travel and {} min of waiting gives {}",
+                        day.day, visit.attraction_id, visit.arrival_time, left_at,
+                        visit.leg.travel_time, visit.wait_minutes, expected
+                    ),
+                ));
+            }
+            left_at = visit.departure_time;
+        }
+        for appointment in appointments {
+            check_appointment_timing(day.day, appointment, left_at)?;
+            left_at = appointment.end;
+        }
+
+        // Check the day has its leg to where it ends, leaving the last stop
+        match (params.day_end_location(day.day), day.return_leg) {
+            (Some(_), None) => {
+                return Err(violation(
+                    ViolationKind::LateReturn,
+                    Some(day.day),
+                    None,
+                    format!("Day {}: no leg to its end point", day.day),
+                ));
+            }
+            (_, Some(leg)) if day.return_arrival_time != Some(left_at + leg.travel_time) => {
+                return Err(violation(
+                    ViolationKind::TimingMismatch,
+                    Some(day.day),
+                    None,
+                    format!(
+                        "Day {}: return arrival is recorded as {:?} but leaving at {} with {} min of travel gives {}",
+                        day.day, day.return_arrival_time, left_at, leg.travel_time, left_at + leg.travel_time
+                    ),
+                ));
+            }
+            _ => {}
         }
 
         // Check daily time budget
-        if !day.visits.is_empty() {
-            let first_arrival = day.visits[0].arrival_time;
-            let last_departure = day.visits.last().unwrap().departure_time;
-            let total_time = last_departure - first_arrival;
+        if let (Some(first), Some(last)) = (day.visits.first(), day.visits.last()) {
+            let total_time = last.departure_time.saturating_sub(first.arrival_time);
             
-            if total_time > params.daily_time_budget {
-                return Err(format!(
-                    "Day {} exceeds time budget: {} > {}",
-                    day.day, total_time, params.daily_time_budget
+            if total_time > params.day_time_budget(day.day) {
+                return Err(violation(
+                    ViolationKind::OverTime,
+                    Some(day.day),
+                    None,
+                    format!(
+                        "Day {} exceeds time budget: {} > {}",
+                        day.day, total_time, params.day_time_budget(day.day)
+                    ),
                 ));
             }
         }
     }
 
+    // Check every day ends in time, the return leg included
+    for day in &itinerary.days {
+        if let Some(arrival) = day.return_arrival_time.filter(|&t| t > params.day_end(day.day)) {
+            return Err(violation(
+                ViolationKind::LateReturn,
+                Some(day.day),
+                None,
+                format!("Day {}: back at {}, after the day ends at {}", day.day, arrival, params.day_end(day.day)),
+            ));
+        }
+    }
+
     // Check total budget
     if itinerary.total_cost > params.total_budget {
-        return Err(format!(
-            "Total cost ${:.2} exceeds budget ${:.2}",
-            itinerary.total_cost, params.total_budget
+        return Err(violation(
+            ViolationKind::OverBudget,
+            None,
+            None,
+            format!(
+                "Total cost ${:.2} exceeds budget ${:.2}",
+                itinerary.total_cost, params.total_budget
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check every visit against the attraction's hours on that day's date,
+/// including weekday hours, holidays and closure dates.
+pub fn verify_opening_hours(
+    itinerary: &Itinerary,
+    attractions: &[Attraction],
+    params: &SolveParams,
+) -> Result<(), Error> {
+    for day in &itinerary.days {
+        let date = params.date_for_day(day.day);
+
+        for visit in &day.visits {
+            let (d, id) = (Some(day.day), Some(visit.attraction_id));
+            let attr = attractions
+                .iter()
+                .find(|a| a.id == visit.attraction_id)
+                .ok_or(Error::UnknownAttraction { id: visit.attraction_id, day: d })?;
+            let hours = attr.hours_on(date, &params.holidays);
+
+            if hours.is_closed() {
+                return Err(violation(
+                    ViolationKind::Closed,
+                    d,
+                    id,
+                    format!(
+                        "Attraction {} is closed on day {}{}",
+                        visit.attraction_id,
+                        day.day,
+                        date.map(|d| format!(" ({})", d)).unwrap_or_default()
+                    ),
+                ));
+            }
+            if !hours.fits_window(visit.arrival_time, visit.departure_time) {
+                return Err(violation(
+                    ViolationKind::OutsideOpeningHours,
+                    d,
+                    id,
+                    format!(
+                        "Visit to attraction {} on day {} ({}-{}) is outside its opening hours",
+                        visit.attraction_id, day.day, visit.arrival_time, visit.departure_time
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `appointment`, reached by its leg leaving at `left_at`, records
+/// the arrival that leg gives and makes its start.
+fn check_appointment_timing(day: u32, appointment: &AppointmentVisit, left_at: u32) -> Result<(), Error> {
+    let arrival = left_at + appointment.leg.travel_time;
+    if appointment.arrival_time != arrival {
+        return Err(violation(
+            ViolationKind::TimingMismatch,
+            Some(day),
+            None,
+            format!(
+                "Day {}: appointment '{}' is reached at {} but leaving at {} with {} min of travel gives {}",
+                day, appointment.name, appointment.arrival_time, left_at, appointment.leg.travel_time, arrival
+            ),
+        ));
+    }
+    if arrival > appointment.start {
+        return Err(violation(
+            ViolationKind::MissedAppointment,
+            Some(day),
+            None,
+            format!(
+                "Day {}: appointment '{}' is reached at {}, after it starts at {}",
+                day, appointment.name, arrival, appointment.start
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn violation(kind: ViolationKind, day: Option<u32>, attraction_id: Option<u32>, message: String) -> Error {
+    Error::InvalidItinerary(Violation { kind, day, attraction_id, message })
+}
+
+/// What kind of rule a visit or day breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationKind {
+    UnknownAttraction,
+    DuplicateVisit,
+    /// Name, fee, preference or category differ from the catalogue.
+    CatalogueMismatch,
+    Closed,
+    OutsideOpeningHours,
+    /// Shorter than the attraction's visit duration.
+    ShortVisit,
+    /// Leaves before it starts, or starts before the previous visit ends.
+    Overlap,
+    /// Arrival does not equal leaving time plus travel plus waiting.
+    TimingMismatch,
+    /// The recorded leg is faster or cheaper than its mode allows, or the
+    /// mode is out of range or not running.
+    InfeasibleLeg,
+    /// Reaches the day's end point (the hotel unless set otherwise) after
+    /// the day's time budget runs out, or has no leg there recorded.
+    LateReturn,
+    /// A visit or appointment ends after the day's time budget runs out,
+    /// or the day's visits span more than it.
+    OverTime,
+    /// An appointment cannot be reached by its start, or a visit overlaps
+    /// it.
+    MissedAppointment,
+    OverBudget,
+    /// Day or trip totals do not add up from the visits.
+    TotalsMismatch,
+}
+
+/// One broken rule, located by day and attraction where it applies.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+pub struct Violation {
+    pub kind: ViolationKind,
+    pub day: Option<u32>,
+    pub attraction_id: Option<u32>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+const TOLERANCE: f64 = 1e-6;
+
+/// Check an itinerary against the catalogue it was planned from and the
+/// travel model, and list every violation rather than stopping at the
+/// first. Covers everything `verify_itinerary` and `verify_opening_hours`
+/// do, plus recorded visit data, leg feasibility, appointments, the final
+/// leg to each day's end point and the reported totals.
+pub fn find_violations(
+    itinerary: &Itinerary,
+    attractions: &[Attraction],
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut report = |kind, day: Option<u32>, attraction_id: Option<u32>, message: String| {
+        violations.push(Violation { kind, day, attraction_id, message });
+    };
+
+    let mut seen_ids = HashSet::new();
+    let (mut cost, mut satisfaction, mut visit_count) = (0.0, 0.0, 0);
+
+    for day in &itinerary.days {
+        let date = params.date_for_day(day.day);
+        let day_end = params.day_end(day.day);
+        let mut left_at = params.day_start_time(day.day);
+        let mut location = Some(params.day_start_location(day.day));
+        let mut last_stop = "the day's start".to_string();
+        let scheduled = params.appointments(day.day);
+        let mut appointments = day.appointments.iter().enumerate().peekable();
+        let (mut day_cost, mut day_fare, mut day_satisfaction, mut day_travel) = (0.0, 0.0, 0.0, 0);
+        for appointment in &day.appointments {
+            day_cost += appointment.leg.fare;
+            day_fare += appointment.leg.fare;
+            day_travel += appointment.leg.travel_time;
+        }
+
+        // Check the recorded appointment against the schedule and the way
+        // there, then move the traveller on to it
+        let attend = |k: usize,
+                          appointment: &AppointmentVisit,
+                          left_at: &mut u32,
+                          location: &mut Option<Location>,
+                          last_stop: &mut String,
+                          report: &mut dyn FnMut(ViolationKind, Option<u32>, Option<u32>, String)| {
+            let problems = check_appointment(appointment, scheduled.get(k), *location, last_stop, *left_at, params, travel);
+            for (kind, problem) in problems {
+                report(kind, Some(day.day), None, format!("Day {}: {}", day.day, problem));
+            }
+            if appointment.end > day_end {
+                report(
+                    ViolationKind::OverTime,
+                    Some(day.day),
+                    None,
+                    format!(
+                        "Day {}: appointment '{}' ends at {}, after the day ends at {}",
+                        day.day, appointment.name, appointment.end, day_end
+                    ),
+                );
+            }
+            *left_at = appointment.end;
+            *location = scheduled.get(k).map(|a| a.location());
+            *last_stop = format!("appointment '{}'", appointment.name);
+        };
+
+        for visit in &day.visits {
+            let (d, id) = (Some(day.day), Some(visit.attraction_id));
+            day_cost += visit.fee + visit.leg.fare;
+            day_fare += visit.leg.fare;
+            day_satisfaction += visit.preference;
+            day_travel += visit.leg.travel_time;
+
+            // Appointments starting before the visit ends come first
+            while let Some((k, appointment)) = appointments.next_if(|(_, a)| a.start < visit.departure_time) {
+                attend(k, appointment, &mut left_at, &mut location, &mut last_stop, &mut report);
+                if visit.arrival_time < appointment.end {
+                    report(
+                        ViolationKind::MissedAppointment,
+                        d,
+                        id,
+                        format!(
+                            "Day {}: attraction {} at {}-{} overlaps appointment '{}' at {}-{}",
+                            day.day, visit.attraction_id, visit.arrival_time, visit.departure_time,
+                            appointment.name, appointment.start, appointment.end
+                        ),
+                    );
+                }
+            }
+
+            if !seen_ids.insert(visit.attraction_id) {
+                report(
+                    ViolationKind::DuplicateVisit,
+                    d,
+                    id,
+                    format!("Duplicate visit to attraction {} on day {}", visit.attraction_id, day.day),
+                );
+            }
+            if visit.arrival_time > visit.departure_time {
+                report(
+                    ViolationKind::Overlap,
+                    d,
+                    id,
+                    format!(
+                        "Day {}: attraction {} starts at {} but ends at {}",
+                        day.day, visit.attraction_id, visit.arrival_time, visit.departure_time
+                    ),
+                );
+            }
+            if visit.departure_time > day_end {
+                report(
+                    ViolationKind::OverTime,
+                    d,
+                    id,
+                    format!(
+                        "Day {}: attraction {} ends at {}, after the day ends at {}",
+                        day.day, visit.attraction_id, visit.departure_time, day_end
+                    ),
+                );
+            }
+            if visit.arrival_time < left_at {
+                report(
+                    ViolationKind::Overlap,
+                    d,
+                    id,
+                    format!(
+                        "Day {}: attraction {} starts at {}, before the previous stop is left at {}",
+                        day.day, visit.attraction_id, visit.arrival_time, left_at
+                    ),
+                );
+            }
+            let expected = left_at + visit.leg.travel_time + visit.wait_minutes;
+            if visit.arrival_time != expected {
+                report(
+                    ViolationKind::TimingMismatch,
+                    d,
+                    id,
+                    format!(
+                        "Day {}: attraction {} starts at {} but leaving at {} with {} min of travel and {} min of waiting gives {}",
+                        day.day, visit.attraction_id, visit.arrival_time, left_at,
+                        visit.leg.travel_time, visit.wait_minutes, expected
+                    ),
+                );
+            }
+
+            let Some(attr) = attractions.iter().find(|a| a.id == visit.attraction_id) else {
+                report(
+                    ViolationKind::UnknownAttraction,
+                    d,
+                    id,
+                    format!("Unknown attraction {} on day {}", visit.attraction_id, day.day),
+                );
+                left_at = visit.departure_time;
+                location = None;
+                last_stop = format!("attraction {}", visit.attraction_id);
+                continue;
+            };
+
+            let category = format!("{:?}", attr.category);
+            if visit.attraction_name != attr.name
+                || (visit.fee - attr.fee).abs() > TOLERANCE
+                || (visit.preference - attr.preference).abs() > TOLERANCE
+                || visit.category != category
+            {
+                report(
+                    ViolationKind::CatalogueMismatch,
+                    d,
+                    id,
+                    format!(
+                        "Day {}: attraction {} is recorded as {:?} ({}, fee ${:.2}, preference {}) but the catalogue has {:?} ({}, fee ${:.2}, preference {})",
+                        day.day, visit.attraction_id, visit.attraction_name, visit.category, visit.fee,
+                        visit.preference, attr.name, category, attr.fee, attr.preference
+                    ),
+                );
+            }
+            if visit.departure_time < visit.arrival_time + attr.duration {
+                report(
+                    ViolationKind::ShortVisit,
+                    d,
+                    id,
+                    format!(
+                        "Day {}: attraction {} is visited for {} min but needs {}",
+                        day.day,
+                        visit.attraction_id,
+                        visit.departure_time.saturating_sub(visit.arrival_time),
+                        attr.duration
+                    ),
+                );
+            }
+
+            let hours = attr.hours_on(date, &params.holidays);
+            if hours.is_closed() {
+                report(
+                    ViolationKind::Closed,
+                    d,
+                    id,
+                    format!(
+                        "Attraction {} is closed on day {}{}",
+                        visit.attraction_id,
+                        day.day,
+                        date.map(|d| format!(" ({})", d)).unwrap_or_default()
+                    ),
+                );
+            } else if !hours.fits_window(visit.arrival_time, visit.departure_time) {
+                report(
+                    ViolationKind::OutsideOpeningHours,
+                    d,
+                    id,
+                    format!(
+                        "Visit to attraction {} on day {} ({}-{}) is outside its opening hours",
+                        visit.attraction_id, day.day, visit.arrival_time, visit.departure_time
+                    ),
+                );
+            }
+
+            if let Some(from) = location {
+                if let Err(problem) = check_leg(&from, &attr.location, left_at, &visit.leg, params, travel) {
+                    report(
+                        ViolationKind::InfeasibleLeg,
+                        d,
+                        id,
+                        format!("Day {}: the leg to attraction {} {}", day.day, visit.attraction_id, problem),
+                    );
+                }
+            }
+
+            left_at = visit.departure_time;
+            location = Some(attr.location);
+            last_stop = format!("attraction {}", visit.attraction_id);
+        }
+
+        for (k, appointment) in appointments {
+            attend(k, appointment, &mut left_at, &mut location, &mut last_stop, &mut report);
+        }
+        for appointment in scheduled.iter().skip(day.appointments.len()) {
+            report(
+                ViolationKind::MissedAppointment,
+                Some(day.day),
+                None,
+                format!(
+                    "Day {}: appointment '{}' at {}-{} is not attended",
+                    day.day, appointment.name, appointment.start, appointment.end
+                ),
+            );
+        }
+
+        // The recorded leg to the day's end point counts like any other;
+        // open-ended days finish where they stand
+        if let Some(leg) = day.return_leg {
+            day_cost += leg.fare;
+            day_fare += leg.fare;
+            day_travel += leg.travel_time;
+        }
+        let last_id = day.visits.last().map(|v| v.attraction_id);
+        if let Some(end) = params.day_end_location(day.day) {
+            match day.return_leg {
+                None => report(
+                    ViolationKind::LateReturn,
+                    Some(day.day),
+                    last_id,
+                    format!("Day {}: no leg from {} to its end point", day.day, last_stop),
+                ),
+                Some(leg) => {
+                    if let Some(from) = location {
+                        if let Err(problem) = check_leg(&from, &end, left_at, &leg, params, travel) {
+                            report(
+                                ViolationKind::InfeasibleLeg,
+                                Some(day.day),
+                                last_id,
+                                format!("Day {}: the leg from {} to its end point {}", day.day, last_stop, problem),
+                            );
+                        }
+                    }
+                    let arrival = left_at + leg.travel_time;
+                    if day.return_arrival_time != Some(arrival) {
+                        report(
+                            ViolationKind::TimingMismatch,
+                            Some(day.day),
+                            last_id,
+                            format!(
+                                "Day {}: return arrival is recorded as {:?} but leaving {} at {} with {} min of travel gives {}",
+                                day.day, day.return_arrival_time, last_stop, left_at, leg.travel_time, arrival
+                            ),
+                        );
+                    }
+                    if arrival > day_end {
+                        report(
+                            ViolationKind::LateReturn,
+                            Some(day.day),
+                            last_id,
+                            format!(
+                                "Day {}: reaches its end point at {} from {}, after the day ends at {}",
+                                day.day, arrival, last_stop, day_end
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        if (day.total_cost - day_cost).abs() > TOLERANCE
+            || (day.total_fare - day_fare).abs() > TOLERANCE
+            || (day.total_satisfaction - day_satisfaction).abs() > TOLERANCE
+            || day.total_travel_time != day_travel
+        {
+            report(
+                ViolationKind::TotalsMismatch,
+                Some(day.day),
+                None,
+                format!(
+                    "Day {} reports cost ${:.2}, fares ${:.2}, satisfaction {:.3} and {} min of travel but its visits add up to ${:.2}, ${:.2}, {:.3} and {} min",
+                    day.day, day.total_cost, day.total_fare, day.total_satisfaction, day.total_travel_time,
+                    day_cost, day_fare, day_satisfaction, day_travel
+                ),
+            );
+        }
+        cost += day_cost;
+        satisfaction += day_satisfaction;
+        visit_count += day.visits.len();
+    }
+
+    if (itinerary.total_cost - cost).abs() > TOLERANCE
+        || (itinerary.total_satisfaction - satisfaction).abs() > TOLERANCE
+        || itinerary.total_attractions != visit_count
+    {
+        report(
+            ViolationKind::TotalsMismatch,
+            None,
+            None,
+            format!(
+                "The trip reports cost ${:.2}, satisfaction {:.3} and {} attractions but its visits add up to ${:.2}, {:.3} and {}",
+                itinerary.total_cost, itinerary.total_satisfaction, itinerary.total_attractions,
+                cost, satisfaction, visit_count
+            ),
+        );
+    }
+    if cost > params.total_budget + TOLERANCE {
+        report(
+            ViolationKind::OverBudget,
+            None,
+            None,
+            format!("Total cost ${:.2} exceeds budget ${:.2}", cost, params.total_budget),
+        );
+    }
+
+    violations
+}
+
+/// What is wrong with `appointment` as recorded, reached by its leg from
+/// `from` (described as `last_stop`) at `left_at`: not the `scheduled` one,
+/// timings that do not add up, a late arrival or an infeasible leg. Legs
+/// from unknown places are not checked.
+fn check_appointment(
+    appointment: &AppointmentVisit,
+    scheduled: Option<&Appointment>,
+    from: Option<Location>,
+    last_stop: &str,
+    left_at: u32,
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+) -> Vec<(ViolationKind, String)> {
+    let mut problems = Vec::new();
+    let name = &appointment.name;
+    match scheduled {
+        None => problems.push((
+            ViolationKind::MissedAppointment,
+            format!("appointment '{}' at {}-{} is not on the schedule", name, appointment.start, appointment.end),
+        )),
+        Some(s) if s.name != *name || s.start != appointment.start || s.end != appointment.end => problems.push((
+            ViolationKind::MissedAppointment,
+            format!(
+                "appointment '{}' at {}-{} is scheduled as '{}' at {}-{}",
+                name, appointment.start, appointment.end, s.name, s.start, s.end
+            ),
+        )),
+        Some(_) => {}
+    }
+
+    let arrival = left_at + appointment.leg.travel_time;
+    if appointment.arrival_time != arrival {
+        problems.push((
+            ViolationKind::TimingMismatch,
+            format!(
+                "appointment '{}' is reached at {} but leaving {} at {} with {} min of travel gives {}",
+                name, appointment.arrival_time, last_stop, left_at, appointment.leg.travel_time, arrival
+            ),
         ));
     }
+    if arrival > appointment.start {
+        problems.push((
+            ViolationKind::MissedAppointment,
+            format!(
+                "leaving {} at {} reaches appointment '{}' at {}, after it starts at {}",
+                last_stop, left_at, name, arrival, appointment.start
+            ),
+        ));
+    }
+    if let (Some(from), Some(s)) = (from, scheduled) {
+        if let Err(problem) = check_leg(&from, &s.location(), left_at, &appointment.leg, params, travel) {
+            problems.push((ViolationKind::InfeasibleLeg, format!("the leg to appointment '{}' {}", name, problem)));
+        }
+    }
+    problems
+}
 
+/// Whether `leg`, leaving `from` at `departure`, is one the traveller's
+/// transport options could actually provide.
+fn check_leg(
+    from: &Location,
+    to: &Location,
+    departure: u32,
+    leg: &Leg,
+    params: &SolveParams,
+    travel: &dyn TravelTimeProvider,
+) -> Result<(), String> {
+    let profile = params
+        .transport
+        .modes
+        .iter()
+        .find(|m| m.mode == leg.mode)
+        .ok_or_else(|| format!("uses {:?}, which is not among the transport options", leg.mode))?;
+    let best = profile
+        .leg(from, to, Some(departure), travel)
+        .ok_or_else(|| format!("is out of range for {:?}", leg.mode))?;
+    if best.travel_time > 0 && !profile.is_available_at(departure) {
+        return Err(format!("leaves at {} when {:?} is not running", departure, leg.mode));
+    }
+    if leg.travel_time < best.travel_time {
+        return Err(format!(
+            "takes {} min by {:?} but needs at least {}",
+            leg.travel_time, leg.mode, best.travel_time
+        ));
+    }
+    if leg.fare + TOLERANCE < best.fare {
+        return Err(format!("costs ${:.2} by {:?} but the fare is ${:.2}", leg.fare, leg.mode, best.fare));
+    }
     Ok(())
 }
@@ -1,7 +1,11 @@
 //! Fetch real Bangkok attraction data from OpenStreetMap Overpass API
 
-use core::models::attraction::{Attraction, Category, Location};
+mod opening_hours;
+
+use core::models::attraction::{Attraction, Category, Location, TimeWindow};
+use opening_hours::{OpeningHours, Schedule};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 
 const BANGKOK_CENTER_LAT: f64 = 13.7563;
@@ -31,7 +35,6 @@ struct Tags {
     tourism: Option<String>,
     amenity: Option<String>,
     #[serde(rename = "opening_hours")]
-    #[allow(dead_code)]
     opening_hours: Option<String>,
 }
 
@@ -73,6 +76,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Convert to our Attraction format
     let mut attractions: Vec<Attraction> = Vec::new();
     let mut id_counter = 1u32;
+    let mut missing_hours = 0;
+    let mut unparsed_hours = Vec::new();
 
     for element in overpass_data.elements {
         if let Some(tags) = element.tags {
@@ -87,6 +92,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 
                 // Generate realistic parameters
                 let (open_time, close_time, duration, fee) = generate_params(&category);
+
+                // Use the OSM hours where we can read them
+                let parsed = match tags.opening_hours.as_deref() {
+                    Some(raw) => match OpeningHours::parse(raw) {
+                        Ok(hours) => Some(hours.to_schedule()),
+                        Err(e) => {
+                            unparsed_hours.push(format!("{}: {}", name, e));
+                            None
+                        }
+                    },
+                    None => {
+                        missing_hours += 1;
+                        None
+                    }
+                };
+                let schedule = parsed.unwrap_or_else(|| Schedule {
+                    windows: vec![TimeWindow::new(open_time, close_time)],
+                    weekday_windows: HashMap::new(),
+                    holiday_windows: None,
+                });
                 
                 let preference = 0.5 + (id_counter as f64 * 0.013) % 0.5; // Spread between 0.5-1.0
 
@@ -97,12 +122,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         lat: element.lat,
                         lng: element.lon,
                     },
-                    open_time,
-                    close_time,
+                    windows: schedule.windows,
                     duration,
                     fee,
                     preference,
                     category,
+                    weekday_windows: schedule.weekday_windows,
+                    holiday_windows: schedule.holiday_windows,
+                    closed_dates: Vec::new(),
                 });
 
                 id_counter += 1;
@@ -126,6 +153,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
     println!("Summary:");
     println!("  - Total attractions: {}", attractions.len());
+    println!(
+        "  - Opening hours from OSM: {}",
+        attractions.len() - missing_hours - unparsed_hours.len()
+    );
+    println!(
+        "  - Category default hours: {} ({} untagged, {} unparsed)",
+        missing_hours + unparsed_hours.len(),
+        missing_hours,
+        unparsed_hours.len()
+    );
+    for reason in &unparsed_hours {
+        println!("      {}", reason);
+    }
     
     let by_category: std::collections::HashMap<String, usize> = attractions
         .iter()
@@ -0,0 +1,241 @@
+//! Parser for the common subset of the OSM `opening_hours` syntax
+//! (https://wiki.openstreetmap.org/wiki/Key:opening_hours).
+//!
+//! Supported: `24/7`, rules separated by `;`, weekday lists and ranges
+//! (`Mo-Fr,Su`), `PH`, several time spans per rule (`09:00-12:00,13:00-17:00`)
+//! and `off`/`closed`. Later rules override earlier ones for the days they
+//! name. Anything else (months, week numbers, sunrise, comments) is rejected
+//! so the caller can fall back to defaults.
+
+use core::chrono::Weekday;
+use core::models::attraction::TimeWindow;
+use std::collections::HashMap;
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("Mo", Weekday::Mon),
+    ("Tu", Weekday::Tue),
+    ("We", Weekday::Wed),
+    ("Th", Weekday::Thu),
+    ("Fr", Weekday::Fri),
+    ("Sa", Weekday::Sat),
+    ("Su", Weekday::Sun),
+];
+
+/// Weekly hours; `None` means the day was never mentioned (closed).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpeningHours {
+    pub days: [Option<Vec<TimeWindow>>; 7],
+    pub holidays: Option<Vec<TimeWindow>>,
+}
+
+/// Attraction schedule fields derived from `OpeningHours`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub windows: Vec<TimeWindow>,
+    pub weekday_windows: HashMap<Weekday, Vec<TimeWindow>>,
+    pub holiday_windows: Option<Vec<TimeWindow>>,
+}
+
+impl OpeningHours {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("empty opening_hours".to_string());
+        }
+        if input == "24/7" {
+            let all_day = Some(vec![TimeWindow::new(0, 1440)]);
+            return Ok(Self { days: std::array::from_fn(|_| all_day.clone()), holidays: None });
+        }
+
+        let mut hours = Self::default();
+        for rule in input.split(';').map(str::trim).filter(|r| !r.is_empty()) {
+            hours.apply_rule(rule)?;
+        }
+        if hours.days.iter().all(|d| d.as_ref().is_none_or(|w| w.is_empty())) {
+            return Err(format!("'{}' is never open", input));
+        }
+        Ok(hours)
+    }
+
+    fn apply_rule(&mut self, rule: &str) -> Result<(), String> {
+        let (selector, times) = match rule.find(|c: char| c.is_ascii_digit()) {
+            Some(i) => (rule[..i].trim(), rule[i..].trim()),
+            None => match rule.rsplit_once(' ') {
+                Some((selector, state)) => (selector.trim(), state.trim()),
+                None if is_closed(rule) => ("", rule),
+                None => (rule, ""),
+            },
+        };
+
+        let windows = if is_closed(times) {
+            Vec::new()
+        } else {
+            parse_times(times)?
+        };
+
+        let (days, holiday) = parse_selector(selector)?;
+        for day in days {
+            self.days[day] = Some(windows.clone());
+        }
+        if holiday {
+            self.holidays = Some(windows);
+        }
+        Ok(())
+    }
+
+    /// Regular `windows` are the most common weekly hours; days that differ
+    /// become `weekday_windows` overrides.
+    pub fn to_schedule(&self) -> Schedule {
+        let week: Vec<Vec<TimeWindow>> =
+            self.days.iter().map(|d| d.clone().unwrap_or_default()).collect();
+
+        let mut regular = &week[0];
+        let mut best = 0;
+        for candidate in week.iter().filter(|w| !w.is_empty()) {
+            let count = week.iter().filter(|w| *w == candidate).count();
+            if count > best {
+                best = count;
+                regular = candidate;
+            }
+        }
+
+        let weekday_windows = WEEKDAYS
+            .iter()
+            .zip(&week)
+            .filter(|(_, windows)| *windows != regular)
+            .map(|((_, day), windows)| (*day, windows.clone()))
+            .collect();
+
+        Schedule {
+            windows: regular.clone(),
+            weekday_windows,
+            holiday_windows: self.holidays.clone(),
+        }
+    }
+}
+
+fn is_closed(state: &str) -> bool {
+    matches!(state, "off" | "closed")
+}
+
+/// Day indices (Monday = 0) and whether `PH` is selected. An empty
+/// selector means every day.
+fn parse_selector(selector: &str) -> Result<(Vec<usize>, bool), String> {
+    if selector.is_empty() {
+        return Ok(((0..7).collect(), false));
+    }
+
+    let mut days = Vec::new();
+    let mut holiday = false;
+    for part in selector.split(',').map(str::trim) {
+        if part == "PH" {
+            holiday = true;
+        } else if let Some((from, to)) = part.split_once('-') {
+            let (from, to) = (weekday_index(from)?, weekday_index(to)?);
+            let mut day = from;
+            loop {
+                days.push(day);
+                if day == to {
+                    break;
+                }
+                day = (day + 1) % 7;  // wraps, e.g. Sa-Mo
+            }
+        } else {
+            days.push(weekday_index(part)?);
+        }
+    }
+    Ok((days, holiday))
+}
+
+fn weekday_index(name: &str) -> Result<usize, String> {
+    WEEKDAYS
+        .iter()
+        .position(|(abbr, _)| *abbr == name)
+        .ok_or_else(|| format!("unsupported selector '{}'", name))
+}
+
+fn parse_times(times: &str) -> Result<Vec<TimeWindow>, String> {
+    let mut windows = Vec::new();
+    for span in times.split(',').map(str::trim) {
+        let (open, close) = span
+            .split_once('-')
+            .ok_or_else(|| format!("expected a time span, got '{}'", span))?;
+        let (open, close) = (parse_time(open)?, parse_time(close)?);
+        // Spans past midnight are cut at the end of the day
+        let close = if close <= open { 1440 } else { close };
+        windows.push(TimeWindow::new(open, close));
+    }
+    windows.sort_by_key(|w| w.open);
+    Ok(windows)
+}
+
+fn parse_time(time: &str) -> Result<u32, String> {
+    let (h, m) = time
+        .trim()
+        .split_once(':')
+        .ok_or_else(|| format!("expected HH:MM, got '{}'", time))?;
+    let parse = |s: &str| s.parse::<u32>().map_err(|_| format!("invalid time '{}'", time));
+    let (h, m) = (parse(h)?, parse(m)?);
+    // 24:00 is the only valid time past 23:59
+    if m >= 60 || h > 24 || (h == 24 && m > 0) {
+        return Err(format!("invalid time '{}'", time));
+    }
+    Ok(h * 60 + m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn w(open: u32, close: u32) -> TimeWindow {
+        TimeWindow::new(open, close)
+    }
+
+    #[test]
+    fn test_weekday_ranges_and_off() {
+        let hours = OpeningHours::parse("Mo-Fr 09:00-17:00; Sa 10:00-14:00; Su off").unwrap();
+        let schedule = hours.to_schedule();
+
+        assert_eq!(schedule.windows, vec![w(540, 1020)]);
+        assert_eq!(schedule.weekday_windows[&Weekday::Sat], vec![w(600, 840)]);
+        assert_eq!(schedule.weekday_windows[&Weekday::Sun], vec![]);
+        assert!(!schedule.weekday_windows.contains_key(&Weekday::Mon));
+    }
+
+    #[test]
+    fn test_split_spans_holidays_and_overrides() {
+        let hours =
+            OpeningHours::parse("08:00-12:00,13:00-17:00; Mo closed; PH 10:00-15:00").unwrap();
+        let schedule = hours.to_schedule();
+
+        assert_eq!(schedule.windows, vec![w(480, 720), w(780, 1020)]);
+        assert_eq!(schedule.weekday_windows.len(), 1);
+        assert_eq!(schedule.weekday_windows[&Weekday::Mon], vec![]);
+        assert_eq!(schedule.holiday_windows, Some(vec![w(600, 900)]));
+    }
+
+    #[test]
+    fn test_always_open_and_past_midnight() {
+        assert_eq!(OpeningHours::parse("24/7").unwrap().to_schedule().windows, vec![w(0, 1440)]);
+
+        let late = OpeningHours::parse("Tu-Su 18:00-02:00").unwrap().to_schedule();
+        assert_eq!(late.windows, vec![w(1080, 1440)]);
+        assert_eq!(late.weekday_windows[&Weekday::Mon], vec![]);
+    }
+
+    #[test]
+    fn test_unsupported_syntax_is_rejected() {
+        for input in ["", "Jan-Mar 09:00-17:00", "sunrise-sunset", "Mo-Fr 9am-5pm", "off"] {
+            assert!(OpeningHours::parse(input).is_err(), "accepted '{}'", input);
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_times_are_rejected() {
+        for input in ["10:75-12:00", "09:60-10:00", "09:00-25:00", "09:00-24:30"] {
+            assert!(OpeningHours::parse(input).is_err(), "accepted '{}'", input);
+        }
+        let late = OpeningHours::parse("18:00-24:00").unwrap().to_schedule();
+        assert_eq!(late.windows, vec![w(1080, 1440)]);
+    }
+}
@@ -1,6 +1,7 @@
-use core::models::attraction::{Attraction, Category, Location};
+use core::models::attraction::{Attraction, Category, Location, TimeWindow};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
 use std::fs;
 
 const BANGKOK_LAT: f64 = 13.7563;
@@ -60,12 +61,14 @@ fn generate_dataset(n: usize, spread: f64) -> Vec<Attraction> {
                     lat: BANGKOK_LAT + rng.gen_range(-spread..spread),
                     lng: BANGKOK_LNG + rng.gen_range(-spread..spread),
                 },
-                open_time: open,
-                close_time: close,
+                windows: vec![TimeWindow::new(open, close)],
                 duration,
                 fee,
                 preference: rng.gen_range(0.3..1.0),
                 category,
+                weekday_windows: HashMap::new(),
+                holiday_windows: None,
+                closed_dates: Vec::new(),
             }
         })
         .collect()
@@ -1,6 +1,10 @@
+use core::algorithms::solver::Capabilities;
 use core::models::itinerary::Itinerary;
 use core::models::constraints::SolveParams;
+use core::utils::experiments::RunOutcome;
+use core::utils::statistics::{Summary, WilcoxonResult};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use utoipa::ToSchema;
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -8,6 +12,13 @@ pub struct SolveRequest {
     pub algorithm: String,
     pub dataset: DatasetSpec,
     pub params: SolveParams,
+    /// Solver settings layered over its `default_config`.
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub config: Option<Value>,
+    /// Return the best itinerary found within this many milliseconds.
+    #[serde(default)]
+    pub time_limit_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -27,12 +38,15 @@ pub struct SolveResponse {
 pub struct BenchmarkRequest {
     pub dataset: DatasetSpec,
     pub params: SolveParams,
+    /// Solvers to compare; defaults to every one that handles the dataset.
+    #[serde(default)]
+    pub algorithms: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct BenchmarkResponse {
-    pub greedy: Itinerary,
-    pub simulated_annealing: Itinerary,
+    /// One itinerary per solver, in registry order.
+    pub results: Vec<Itinerary>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -40,25 +54,59 @@ pub struct ExperimentRequest {
     pub dataset: String,
     pub n_runs: usize,
     pub params: SolveParams,
+    /// Solvers to run; defaults to every non-exact one that handles the
+    /// dataset.
+    #[serde(default)]
+    pub algorithms: Option<Vec<String>>,
+    /// Threads to spread the runs over; defaults to every core. Results
+    /// are the same for any value, but `1` keeps the timings undisturbed.
+    #[serde(default)]
+    pub threads: Option<usize>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ExperimentResponse {
     pub dataset_name: String,
     pub dataset_size: usize,
-    pub greedy: AlgorithmStats,
-    pub sa: AlgorithmStats,
-    pub sa_improvement_pct: f64,
+    /// One entry per solver, in registry order.
+    pub algorithms: Vec<AlgorithmStats>,
+    /// Exact solver result, for datasets small enough to run it on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact: Option<ExactStats>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExactStats {
+    pub satisfaction: f64,
+    pub upper_bound: f64,
+    pub proven_optimal: bool,
+    pub nodes: u64,
+    pub ms: u128,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AlgorithmStats {
+    pub algorithm: String,
     pub mean_satisfaction: f64,
     pub std_satisfaction: f64,
     pub mean_ms: f64,
     pub valid_runs: usize,
     pub mean_attractions: f64,
     pub mean_cost: f64,
+    /// Shortfall from the exact solver's bound, in percent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimality_gap_pct: Option<f64>,
+    /// Mean satisfaction relative to the greedy baseline, in percent.
+    pub improvement_pct: f64,
+    /// Quartiles, range and bootstrap 95% CI of satisfaction over the valid
+    /// runs.
+    pub satisfaction: Summary,
+    /// Runs whose itinerary failed verification, with the reason.
+    pub invalid_runs: Vec<RunOutcome>,
+    /// Wilcoxon signed-rank test against greedy on the same seeds; absent
+    /// for greedy itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vs_greedy: Option<WilcoxonResult>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -66,3 +114,25 @@ pub struct DatasetInfo {
     pub name: String,
     pub size: usize,
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AlgorithmInfo {
+    pub name: String,
+    pub label: String,
+    pub capabilities: Capabilities,
+    #[schema(value_type = Object)]
+    pub default_config: Value,
+}
+
+/// Work to run as a background job.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobRequest {
+    Solve(SolveRequest),
+    Experiment(ExperimentRequest),
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobSubmitted {
+    pub id: u64,
+}
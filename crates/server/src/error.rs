@@ -0,0 +1,175 @@
+//! Errors returned by the routes, each with its HTTP status and a JSON body
+//! naming what went wrong.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use core::models::constraints::ParamError;
+use core::utils::verifier::Violation;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug)]
+pub enum ApiError {
+    UnknownDataset(String),
+    UnknownAlgorithm(String),
+    /// The route only runs some solvers, e.g. streaming is SA only.
+    UnsupportedAlgorithm { algorithm: String, reason: &'static str },
+    JobNotFound(u64),
+    Core(core::Error),
+    /// The blocking task doing the work panicked.
+    Internal(String),
+}
+
+/// Body of every error response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    /// Machine-readable kind, e.g. `unknown_dataset` or `invalid_config`.
+    pub error: &'static str,
+    pub message: String,
+    /// Every problem with the parameters, for `invalid_params`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problems: Option<Vec<ParamError>>,
+    /// The broken rule, for `invalid_itinerary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violation: Option<Violation>,
+    /// File and position, for `dataset_error`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<ErrorLocation>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorLocation {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::UnknownDataset(_) | ApiError::JobNotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::UnknownAlgorithm(_) | ApiError::UnsupportedAlgorithm { .. } => StatusCode::BAD_REQUEST,
+            ApiError::Core(e) => match e {
+                core::Error::InvalidConfig { .. } => StatusCode::BAD_REQUEST,
+                core::Error::InvalidParams(_)
+                | core::Error::UnknownAttraction { .. }
+                | core::Error::Infeasible(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                core::Error::InvalidItinerary(_) | core::Error::Dataset { .. } => {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            },
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            ApiError::UnknownDataset(_) => "unknown_dataset",
+            ApiError::UnknownAlgorithm(_) => "unknown_algorithm",
+            ApiError::UnsupportedAlgorithm { .. } => "unsupported_algorithm",
+            ApiError::JobNotFound(_) => "job_not_found",
+            ApiError::Core(e) => match e {
+                core::Error::InvalidParams(_) => "invalid_params",
+                core::Error::InvalidConfig { .. } => "invalid_config",
+                core::Error::UnknownAttraction { .. } => "unknown_attraction",
+                core::Error::Infeasible(_) => "infeasible",
+                core::Error::InvalidItinerary(_) => "invalid_itinerary",
+                core::Error::Dataset { .. } => "dataset_error",
+            },
+            ApiError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn body(&self) -> ErrorBody {
+        let mut body = ErrorBody {
+            error: self.kind(),
+            message: self.to_string(),
+            problems: None,
+            violation: None,
+            location: None,
+        };
+        match self {
+            ApiError::Core(core::Error::InvalidParams(problems)) => body.problems = Some(problems.clone()),
+            ApiError::Core(core::Error::InvalidItinerary(violation)) => body.violation = Some(violation.clone()),
+            ApiError::Core(core::Error::Dataset { path, line, column, .. }) => {
+                body.location = Some(ErrorLocation { path: path.clone(), line: *line, column: *column });
+            }
+            _ => {}
+        }
+        body
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::UnknownDataset(name) => write!(f, "Dataset '{}' not found", name),
+            ApiError::UnknownAlgorithm(name) => write!(f, "Unknown algorithm '{}'", name),
+            ApiError::UnsupportedAlgorithm { algorithm, reason } => {
+                write!(f, "Algorithm '{}' is not supported here: {}", algorithm, reason)
+            }
+            ApiError::JobNotFound(id) => write!(f, "Job {} not found", id),
+            ApiError::Core(e) => write!(f, "{}", e),
+            ApiError::Internal(message) => write!(f, "Internal error: {}", message),
+        }
+    }
+}
+
+impl From<core::Error> for ApiError {
+    fn from(e: core::Error) -> Self {
+        ApiError::Core(e)
+    }
+}
+
+impl From<tokio::task::JoinError> for ApiError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        ApiError::Internal(e.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self.body())).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_errors_map_to_statuses() {
+        let config = ApiError::from(core::Error::InvalidConfig {
+            solver: "genetic".to_string(),
+            message: "unknown field".to_string(),
+        });
+        assert_eq!(config.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(config.body().error, "invalid_config");
+
+        let infeasible = ApiError::from(core::Error::Infeasible("too large".to_string()));
+        assert_eq!(infeasible.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let dataset = ApiError::from(core::Error::Dataset {
+            path: "data/datasets/small.json".to_string(),
+            line: Some(3),
+            column: Some(7),
+            message: "expected value".to_string(),
+        });
+        assert_eq!(dataset.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = serde_json::to_value(dataset.body()).unwrap();
+        assert_eq!(body["location"]["line"], 3);
+        assert_eq!(body["error"], "dataset_error");
+
+        assert_eq!(ApiError::UnknownDataset("nope".to_string()).status(), StatusCode::NOT_FOUND);
+
+        let params = core::SolveParams { num_days: 0, total_budget: -1.0, ..Default::default() };
+        let invalid = ApiError::from(core::Error::InvalidParams(params.validate().unwrap_err()));
+        assert_eq!(invalid.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = serde_json::to_value(invalid.body()).unwrap();
+        assert_eq!(body["problems"][0]["field"], "num_days");
+        assert_eq!(body["problems"][1]["field"], "total_budget");
+    }
+}
@@ -0,0 +1,318 @@
+//! Background jobs for solves and experiments that take too long to hold
+//! a request open. Work runs on Tokio's blocking pool, at most
+//! `max_concurrent` jobs at a time.
+
+use core::algorithms::solver::StopToken;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+use utoipa::ToSchema;
+
+/// Finished jobs kept for `GET /api/jobs/{id}`; older ones are dropped.
+const MAX_FINISHED_JOBS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled)
+    }
+}
+
+/// Units of work done so far, shared with the running job.
+#[derive(Debug, Default)]
+pub struct Progress {
+    done: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        Self { done: AtomicUsize::new(0), total: AtomicUsize::new(total) }
+    }
+
+    pub fn advance(&self) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn finish(&self) {
+        self.done.store(self.total.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JobInfo {
+    pub id: u64,
+    /// `solve` or `experiment`.
+    pub kind: String,
+    pub status: JobStatus,
+    pub done: usize,
+    pub total: usize,
+    /// Milliseconds since the Unix epoch.
+    pub created_at: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u128>,
+    /// `SolveResponse` or `ExperimentResponse`, once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+struct Job {
+    kind: &'static str,
+    status: JobStatus,
+    progress: Arc<Progress>,
+    stop: StopToken,
+    created_at: u128,
+    finished_at: Option<u128>,
+    result: Option<Value>,
+    error: Option<String>,
+}
+
+impl Job {
+    fn info(&self, id: u64, with_result: bool) -> JobInfo {
+        JobInfo {
+            id,
+            kind: self.kind.to_string(),
+            status: self.status,
+            done: self.progress.done.load(Ordering::Relaxed),
+            total: self.progress.total.load(Ordering::Relaxed),
+            created_at: self.created_at,
+            finished_at: self.finished_at,
+            result: if with_result { self.result.clone() } else { None },
+            error: self.error.clone(),
+        }
+    }
+}
+
+pub struct JobQueue {
+    jobs: Mutex<HashMap<u64, Job>>,
+    next_id: AtomicU64,
+    permits: Arc<Semaphore>,
+    pub max_concurrent: usize,
+}
+
+impl JobQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            jobs: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+        }
+    }
+
+    /// Queue `work` and return the job's id. `work` runs on the blocking
+    /// pool once a slot is free and should give up when the token fires.
+    pub fn submit<F>(self: &Arc<Self>, kind: &'static str, total: usize, work: F) -> u64
+    where
+        F: FnOnce(&Progress, &StopToken) -> Result<Value, String> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let progress = Arc::new(Progress::new(total));
+        let stop = StopToken::new();
+        self.jobs.lock().unwrap().insert(
+            id,
+            Job {
+                kind,
+                status: JobStatus::Queued,
+                progress: progress.clone(),
+                stop: stop.clone(),
+                created_at: now_ms(),
+                finished_at: None,
+                result: None,
+                error: None,
+            },
+        );
+
+        let queue = self.clone();
+        tokio::spawn(async move {
+            let _permit = queue.permits.clone().acquire_owned().await;
+            if !queue.start(id) {
+                return;
+            }
+
+            let outcome = {
+                let (progress, stop) = (progress.clone(), stop.clone());
+                tokio::task::spawn_blocking(move || work(&progress, &stop)).await
+            };
+            let outcome = match outcome {
+                Ok(outcome) => outcome,
+                Err(e) => Err(format!("Job panicked: {}", e)),
+            };
+            queue.finish(id, outcome);
+        });
+
+        id
+    }
+
+    pub fn get(&self, id: u64) -> Option<JobInfo> {
+        self.jobs.lock().unwrap().get(&id).map(|job| job.info(id, true))
+    }
+
+    /// Every job without its result, newest first.
+    pub fn list(&self) -> Vec<JobInfo> {
+        let jobs = self.jobs.lock().unwrap();
+        let mut list: Vec<JobInfo> = jobs.iter().map(|(&id, job)| job.info(id, false)).collect();
+        list.sort_by_key(|job| std::cmp::Reverse(job.id));
+        list
+    }
+
+    /// Cancel a job. Queued jobs never start; running ones stop at their
+    /// next check and are reported as cancelled.
+    pub fn cancel(&self, id: u64) -> Option<JobInfo> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.get_mut(&id)?;
+        if !job.status.is_finished() {
+            job.stop.cancel();
+            if job.status == JobStatus::Queued {
+                job.status = JobStatus::Cancelled;
+                job.finished_at = Some(now_ms());
+            }
+        }
+        Some(job.info(id, false))
+    }
+
+    /// Mark a queued job as running; false if it was cancelled meanwhile.
+    fn start(&self, id: u64) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        match jobs.get_mut(&id) {
+            Some(job) if job.status == JobStatus::Queued => {
+                job.status = JobStatus::Running;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn finish(&self, id: u64, outcome: Result<Value, String>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.get_mut(&id) {
+            job.finished_at = Some(now_ms());
+            job.status = match outcome {
+                _ if job.stop.is_cancelled() => JobStatus::Cancelled,
+                Ok(result) => {
+                    job.progress.finish();
+                    job.result = Some(result);
+                    JobStatus::Completed
+                }
+                Err(error) => {
+                    job.error = Some(error);
+                    JobStatus::Failed
+                }
+            };
+        }
+
+        let mut finished: Vec<(u128, u64)> = jobs
+            .iter()
+            .filter_map(|(&id, job)| job.finished_at.map(|at| (at, id)))
+            .collect();
+        if finished.len() > MAX_FINISHED_JOBS {
+            finished.sort();
+            for (_, id) in &finished[..finished.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+    }
+}
+
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    // `#[tokio::test]` expands to paths into `::core`, which the `core`
+    // crate shadows here
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Runtime::new().unwrap().block_on(future)
+    }
+
+    async fn wait_until_finished(queue: &JobQueue, id: u64) -> JobInfo {
+        for _ in 0..500 {
+            let info = queue.get(id).unwrap();
+            if info.status.is_finished() {
+                return info;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not finish", id);
+    }
+
+    #[test]
+    fn test_job_completes_with_progress() {
+        block_on(async {
+            let queue = Arc::new(JobQueue::new(2));
+            let id = queue.submit("solve", 3, |progress, _stop| {
+                for _ in 0..3 {
+                    progress.advance();
+                }
+                Ok(serde_json::json!({ "answer": 42 }))
+            });
+
+            let info = wait_until_finished(&queue, id).await;
+            assert_eq!(info.status, JobStatus::Completed);
+            assert_eq!((info.done, info.total), (3, 3));
+            assert_eq!(info.result.unwrap()["answer"], 42);
+            assert!(queue.list()[0].result.is_none());
+        });
+    }
+
+    #[test]
+    fn test_cancel_running_and_queued_jobs() {
+        block_on(async {
+            let queue = Arc::new(JobQueue::new(1));
+            let running = queue.submit("experiment", 1, |_progress, stop| {
+                while !stop.should_stop() {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Ok(Value::Null)
+            });
+            let queued = queue.submit("solve", 1, |_progress, _stop| Ok(Value::Null));
+
+            // Only one slot, so the second job waits
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(queue.get(running).unwrap().status, JobStatus::Running);
+            assert_eq!(queue.get(queued).unwrap().status, JobStatus::Queued);
+
+            assert_eq!(queue.cancel(queued).unwrap().status, JobStatus::Cancelled);
+            queue.cancel(running).unwrap();
+            let info = wait_until_finished(&queue, running).await;
+            assert_eq!(info.status, JobStatus::Cancelled);
+            assert!(info.result.is_none());
+            assert!(queue.cancel(999).is_none());
+        });
+    }
+
+    #[test]
+    fn test_failed_job_reports_error() {
+        block_on(async {
+            let queue = Arc::new(JobQueue::new(1));
+            let id = queue.submit("solve", 1, |_progress, _stop| Err("Dataset not found".to_string()));
+            let info = wait_until_finished(&queue, id).await;
+            assert_eq!(info.status, JobStatus::Failed);
+            assert_eq!(info.error.as_deref(), Some("Dataset not found"));
+        });
+    }
+}
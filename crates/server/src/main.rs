@@ -1,4 +1,6 @@
 mod api_types;
+mod error;
+mod jobs;
 mod routes;
 mod state;
 
@@ -16,9 +18,15 @@ use utoipa_swagger_ui::SwaggerUi;
 #[openapi(
     paths(
         routes::datasets::list_datasets,
+        routes::algorithms::list_algorithms,
         routes::solve::solve,
+        routes::solve::solve_stream,
         routes::benchmark::benchmark,
         routes::experiment::experiment,
+        routes::jobs::submit_job,
+        routes::jobs::list_jobs,
+        routes::jobs::get_job,
+        routes::jobs::cancel_job,
     ),
     components(schemas(
         api_types::SolveRequest,
@@ -29,19 +37,47 @@ use utoipa_swagger_ui::SwaggerUi;
         api_types::ExperimentResponse,
         api_types::DatasetSpec,
         api_types::AlgorithmStats,
+        api_types::ExactStats,
         api_types::DatasetInfo,
+        api_types::AlgorithmInfo,
+        api_types::JobRequest,
+        api_types::JobSubmitted,
+        error::ErrorBody,
+        error::ErrorLocation,
+        jobs::JobInfo,
+        jobs::JobStatus,
+        core::algorithms::solver::Capabilities,
+        core::utils::experiments::RunOutcome,
+        core::utils::statistics::Summary,
+        core::utils::statistics::WilcoxonResult,
         core::models::constraints::SolveParams,
+        core::models::constraints::DayOverride,
+        core::models::constraints::Appointment,
+        core::models::constraints::ParamError,
         core::models::itinerary::Itinerary,
         core::models::itinerary::DayPlan,
         core::models::itinerary::Visit,
+        core::models::itinerary::AppointmentVisit,
         core::models::itinerary::ConvergencePoint,
+        core::models::transport::TransportOptions,
+        core::models::transport::ModeProfile,
+        core::models::transport::TransportMode,
+        core::models::transport::Leg,
+        core::utils::verifier::Violation,
+        core::utils::verifier::ViolationKind,
     ))
 )]
 struct ApiDoc;
 
 #[tokio::main]
 async fn main() {
-    let state = Arc::new(AppState::new());
+    let state = match AppState::new() {
+        Ok(state) => Arc::new(state),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
 
     let cors = CorsLayer::new()
         .allow_origin(Any)
@@ -51,11 +87,15 @@ async fn main() {
     let app = Router::new()
         .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .route("/api/datasets", get(routes::datasets::list_datasets))
+        .route("/api/algorithms", get(routes::algorithms::list_algorithms))
         .route("/api/solve", post(routes::solve::solve))
+        .route("/api/solve/stream", post(routes::solve::solve_stream))
         .route("/api/benchmark", post(routes::benchmark::benchmark))
         .route("/api/experiment", post(routes::experiment::experiment))
+        .route("/api/jobs", post(routes::jobs::submit_job).get(routes::jobs::list_jobs))
+        .route("/api/jobs/:id", get(routes::jobs::get_job).delete(routes::jobs::cancel_job))
         .layer(cors)
-        .with_state(state);
+        .with_state(state.clone());
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
         .await
@@ -63,6 +103,7 @@ async fn main() {
     
     println!("🚀 Server running at http://localhost:3000");
     println!("📚 API docs at http://localhost:3000/docs");
+    println!("⚙️  Running up to {} background jobs at once", state.jobs.max_concurrent);
     
     axum::serve(listener, app).await.unwrap();
 }
@@ -0,0 +1,26 @@
+use crate::api_types::AlgorithmInfo;
+use crate::state::AppState;
+use axum::{extract::State, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/api/algorithms",
+    responses(
+        (status = 200, description = "Registered solvers", body = Vec<AlgorithmInfo>)
+    )
+)]
+pub async fn list_algorithms(State(state): State<Arc<AppState>>) -> Json<Vec<AlgorithmInfo>> {
+    let algorithms = state
+        .solvers
+        .iter()
+        .map(|solver| AlgorithmInfo {
+            name: solver.name().to_string(),
+            label: solver.label().to_string(),
+            capabilities: solver.capabilities(),
+            default_config: solver.default_config(),
+        })
+        .collect();
+
+    Json(algorithms)
+}
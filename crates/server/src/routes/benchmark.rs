@@ -1,7 +1,9 @@
 use crate::api_types::{BenchmarkRequest, BenchmarkResponse};
+use crate::error::ApiError;
 use crate::state::AppState;
-use axum::{extract::State, http::StatusCode, Json};
-use core::algorithms::{greedy_solve, sa_solve};
+use axum::{extract::State, Json};
+use core::algorithms::solver::check_instance;
+use core::utils::parallel::{default_threads, map_indexed};
 use std::sync::Arc;
 
 #[utoipa::path(
@@ -10,23 +12,36 @@ use std::sync::Arc;
     request_body = BenchmarkRequest,
     responses(
         (status = 200, description = "Benchmark results", body = BenchmarkResponse),
-        (status = 404, description = "Dataset not found")
+        (status = 400, description = "Unknown algorithm", body = ErrorBody),
+        (status = 404, description = "Dataset not found", body = ErrorBody),
+        (status = 422, description = "Unusable parameters", body = ErrorBody)
     )
 )]
 pub async fn benchmark(
     State(state): State<Arc<AppState>>,
     Json(req): Json<BenchmarkRequest>,
-) -> Result<Json<BenchmarkResponse>, StatusCode> {
+) -> Result<Json<BenchmarkResponse>, ApiError> {
     let attractions = state
         .datasets
         .get(&req.dataset.name)
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .ok_or_else(|| ApiError::UnknownDataset(req.dataset.name.clone()))?;
+    let params = state.prepare_params(req.params)?;
+    let solvers = state.select_solvers(req.algorithms.as_deref(), attractions.len(), true)?;
+    let travel = state.travel_for(&req.dataset.name);
+    for solver in &solvers {
+        check_instance(solver.as_ref(), attractions, &params)?;
+    }
 
-    let greedy = greedy_solve(attractions, &req.params, 42);
-    let sa = sa_solve(attractions, &req.params, 42);
+    let results = tokio::task::spawn_blocking(move || {
+        let attractions = &state.datasets[&req.dataset.name];
+        // At most one thread per core, so solvers only share a core once
+        // there are more of them than cores
+        let threads = solvers.len().min(default_threads());
+        map_indexed(solvers.len(), threads, |i| {
+            solvers[i].solve_default(attractions, &params, 42, travel.as_ref())
+        })
+    })
+    .await?;
 
-    Ok(Json(BenchmarkResponse {
-        greedy,
-        simulated_annealing: sa,
-    }))
+    Ok(Json(BenchmarkResponse { results }))
 }
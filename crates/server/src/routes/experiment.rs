@@ -1,67 +1,167 @@
-use crate::api_types::{AlgorithmStats, ExperimentRequest, ExperimentResponse};
+use crate::api_types::{AlgorithmStats, ExactStats, ExperimentRequest, ExperimentResponse};
+use crate::error::ApiError;
+use crate::jobs::Progress;
 use crate::state::AppState;
-use axum::{extract::State, http::StatusCode, Json};
-use core::algorithms::{greedy_solve, sa_solve};
-use core::utils::experiments::run_experiments;
+use axum::{extract::State, Json};
+use core::algorithms::branch_and_bound::{self, MAX_EXACT_ATTRACTIONS, MAX_NODES};
+use core::algorithms::greedy::GreedySolver;
+use core::algorithms::solver::{check_instance, DynSolver, Solver, StopToken};
+use core::models::constraints::SolveParams;
+use core::models::travel::TravelTimeProvider;
+use core::utils::experiments::run_experiments_parallel;
+use core::utils::parallel::default_threads;
 use std::sync::Arc;
 
+/// A validated experiment request, ready to run on a blocking thread.
+pub struct PreparedExperiment {
+    state: Arc<AppState>,
+    dataset: String,
+    n_runs: usize,
+    threads: usize,
+    solvers: Vec<Arc<dyn DynSolver>>,
+    params: SolveParams,
+    travel: Arc<dyn TravelTimeProvider>,
+}
+
+impl PreparedExperiment {
+    pub fn new(state: &Arc<AppState>, req: ExperimentRequest) -> Result<Self, ApiError> {
+        let attractions = state
+            .datasets
+            .get(&req.dataset)
+            .ok_or_else(|| ApiError::UnknownDataset(req.dataset.clone()))?;
+        let params = state.prepare_params(req.params)?;
+        let solvers = state.select_solvers(req.algorithms.as_deref(), attractions.len(), false)?;
+        for solver in &solvers {
+            check_instance(solver.as_ref(), attractions, &params)?;
+        }
+
+        Ok(Self {
+            state: state.clone(),
+            travel: state.travel_for(&req.dataset),
+            dataset: req.dataset,
+            n_runs: req.n_runs,
+            threads: req.threads.unwrap_or_else(default_threads),
+            solvers,
+            params,
+        })
+    }
+
+    /// Number of solver runs `run` will report progress for.
+    pub fn total_runs(&self) -> usize {
+        self.solvers.len() * self.n_runs
+    }
+
+    /// Run every solver `n_runs` times. Once `stop` fires the remaining
+    /// runs return straight away, so the result is only partial.
+    pub fn run(self, progress: &Progress, stop: &StopToken) -> ExperimentResponse {
+        let attractions = &self.state.datasets[&self.dataset];
+        let params = &self.params;
+        let travel = self.travel.as_ref();
+
+        let stats: Vec<_> = self
+            .solvers
+            .iter()
+            .map(|solver| {
+                let run = |attrs: &[_], params: &_, seed| {
+                    let itinerary = solver
+                        .solve(attrs, params, None, seed, travel, stop)
+                        .expect("instance was checked");
+                    progress.advance();
+                    itinerary
+                };
+                let stats = run_experiments_parallel(attractions, params, travel, self.n_runs, self.threads, run);
+                (solver.name(), stats)
+            })
+            .collect();
+
+        // Improvements and significance are measured against greedy on the
+        // same seeds, running it if it was not requested
+        let greedy_name = Solver::name(&GreedySolver);
+        let fallback = (!stats.iter().any(|(name, _)| *name == greedy_name)).then(|| {
+            run_experiments_parallel(attractions, params, travel, self.n_runs, self.threads, |attrs, params, seed| {
+                Solver::solve(&GreedySolver, attrs, params, &Default::default(), seed, travel, stop)
+            })
+        });
+        let greedy = fallback
+            .as_ref()
+            .or_else(|| stats.iter().find(|(name, _)| *name == greedy_name).map(|(_, stats)| stats))
+            .expect("greedy was run");
+        let baseline = greedy.mean_satisfaction;
+        let comparisons: Vec<_> = stats
+            .iter()
+            .map(|(name, stats)| (*name != greedy_name).then(|| stats.compare(greedy)))
+            .collect();
+
+        // Measure the optimality gap where the exact solver is affordable
+        let exact = (attractions.len() <= MAX_EXACT_ATTRACTIONS).then(|| {
+            branch_and_bound::solve_exact(attractions, params, travel, MAX_NODES, stop)
+        });
+
+        let algorithms = stats
+            .into_iter()
+            .zip(comparisons)
+            .map(|((name, stats), vs_greedy)| {
+                let stats = match &exact {
+                    Some(exact) => stats.with_upper_bound(exact.upper_bound),
+                    None => stats,
+                };
+                let improvement = if baseline > 0.0 {
+                    ((stats.mean_satisfaction - baseline) / baseline) * 100.0
+                } else {
+                    0.0
+                };
+                AlgorithmStats {
+                    algorithm: name.to_string(),
+                    mean_satisfaction: stats.mean_satisfaction,
+                    std_satisfaction: stats.std_satisfaction,
+                    mean_ms: stats.mean_ms,
+                    valid_runs: stats.valid_runs,
+                    mean_attractions: stats.mean_attractions,
+                    mean_cost: stats.mean_cost,
+                    optimality_gap_pct: stats.optimality_gap_pct,
+                    improvement_pct: improvement,
+                    satisfaction: stats.satisfaction,
+                    invalid_runs: stats.invalid_runs().cloned().collect(),
+                    vs_greedy,
+                }
+            })
+            .collect();
+
+        ExperimentResponse {
+            dataset_size: attractions.len(),
+            dataset_name: self.dataset,
+            algorithms,
+            exact: exact.map(|exact| ExactStats {
+                satisfaction: exact.itinerary.total_satisfaction,
+                upper_bound: exact.upper_bound,
+                proven_optimal: exact.proven_optimal,
+                nodes: exact.nodes,
+                ms: exact.itinerary.computation_ms,
+            }),
+        }
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/experiment",
     request_body = ExperimentRequest,
     responses(
         (status = 200, description = "Experiment results", body = ExperimentResponse),
-        (status = 404, description = "Dataset not found")
+        (status = 400, description = "Unknown algorithm", body = ErrorBody),
+        (status = 404, description = "Dataset not found", body = ErrorBody),
+        (status = 422, description = "Unusable parameters", body = ErrorBody)
     )
 )]
 pub async fn experiment(
     State(state): State<Arc<AppState>>,
     Json(req): Json<ExperimentRequest>,
-) -> Result<Json<ExperimentResponse>, StatusCode> {
-    let attractions = state
-        .datasets
-        .get(&req.dataset)
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    let greedy_stats = run_experiments(
-        attractions,
-        &req.params,
-        req.n_runs,
-        |attrs, params, seed| greedy_solve(attrs, params, seed),
-    );
-
-    let sa_stats = run_experiments(
-        attractions,
-        &req.params,
-        req.n_runs,
-        |attrs, params, seed| sa_solve(attrs, params, seed),
-    );
-
-    let improvement = if greedy_stats.mean_satisfaction > 0.0 {
-        ((sa_stats.mean_satisfaction - greedy_stats.mean_satisfaction) / greedy_stats.mean_satisfaction) * 100.0
-    } else {
-        0.0
-    };
+) -> Result<Json<ExperimentResponse>, ApiError> {
+    let prepared = PreparedExperiment::new(&state, req)?;
+    let response = tokio::task::spawn_blocking(move || {
+        prepared.run(&Progress::default(), &StopToken::new())
+    })
+    .await?;
 
-    Ok(Json(ExperimentResponse {
-        dataset_name: req.dataset,
-        dataset_size: attractions.len(),
-        greedy: AlgorithmStats {
-            mean_satisfaction: greedy_stats.mean_satisfaction,
-            std_satisfaction: greedy_stats.std_satisfaction,
-            mean_ms: greedy_stats.mean_ms,
-            valid_runs: greedy_stats.valid_runs,
-            mean_attractions: greedy_stats.mean_attractions,
-            mean_cost: greedy_stats.mean_cost,
-        },
-        sa: AlgorithmStats {
-            mean_satisfaction: sa_stats.mean_satisfaction,
-            std_satisfaction: sa_stats.std_satisfaction,
-            mean_ms: sa_stats.mean_ms,
-            valid_runs: sa_stats.valid_runs,
-            mean_attractions: sa_stats.mean_attractions,
-            mean_cost: sa_stats.mean_cost,
-        },
-        sa_improvement_pct: improvement,
-    }))
+    Ok(Json(response))
 }
@@ -0,0 +1,93 @@
+use crate::api_types::{ExperimentRequest, JobRequest, JobSubmitted, SolveRequest, SolveResponse};
+use crate::error::ApiError;
+use crate::jobs::JobInfo;
+use crate::routes::experiment::PreparedExperiment;
+use crate::routes::solve::PreparedSolve;
+use crate::state::AppState;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/api/jobs",
+    request_body = JobRequest,
+    responses(
+        (status = 202, description = "Job queued", body = JobSubmitted),
+        (status = 400, description = "Unknown algorithm or invalid config", body = ErrorBody),
+        (status = 404, description = "Dataset not found", body = ErrorBody),
+        (status = 422, description = "Unusable parameters", body = ErrorBody)
+    )
+)]
+pub async fn submit_job(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<JobRequest>,
+) -> Result<(StatusCode, Json<JobSubmitted>), ApiError> {
+    let id = match req {
+        JobRequest::Solve(req) => submit_solve(&state, req)?,
+        JobRequest::Experiment(req) => submit_experiment(&state, req)?,
+    };
+    Ok((StatusCode::ACCEPTED, Json(JobSubmitted { id })))
+}
+
+fn submit_solve(state: &Arc<AppState>, req: SolveRequest) -> Result<u64, ApiError> {
+    let prepared = PreparedSolve::new(state, req)?;
+    Ok(state.jobs.submit("solve", 1, move |_progress, stop| {
+        let itinerary = prepared.run(stop).map_err(|e| e.to_string())?;
+        serde_json::to_value(SolveResponse { itinerary }).map_err(|e| e.to_string())
+    }))
+}
+
+fn submit_experiment(state: &Arc<AppState>, req: ExperimentRequest) -> Result<u64, ApiError> {
+    let prepared = PreparedExperiment::new(state, req)?;
+    let total = prepared.total_runs();
+    Ok(state.jobs.submit("experiment", total, move |progress, stop| {
+        serde_json::to_value(prepared.run(progress, stop)).map_err(|e| e.to_string())
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/jobs",
+    responses(
+        (status = 200, description = "All jobs, newest first, without results", body = Vec<JobInfo>)
+    )
+)]
+pub async fn list_jobs(State(state): State<Arc<AppState>>) -> Json<Vec<JobInfo>> {
+    Json(state.jobs.list())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/jobs/{id}",
+    params(("id" = u64, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "Job status, progress and result", body = JobInfo),
+        (status = 404, description = "Job not found", body = ErrorBody)
+    )
+)]
+pub async fn get_job(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u64>,
+) -> Result<Json<JobInfo>, ApiError> {
+    state.jobs.get(id).map(Json).ok_or(ApiError::JobNotFound(id))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/jobs/{id}",
+    params(("id" = u64, Path, description = "Job id")),
+    responses(
+        (status = 200, description = "Cancellation requested", body = JobInfo),
+        (status = 404, description = "Job not found", body = ErrorBody)
+    )
+)]
+pub async fn cancel_job(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u64>,
+) -> Result<Json<JobInfo>, ApiError> {
+    state.jobs.cancel(id).map(Json).ok_or(ApiError::JobNotFound(id))
+}
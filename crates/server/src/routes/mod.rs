@@ -1,4 +1,6 @@
+pub mod algorithms;
 pub mod datasets;
 pub mod solve;
 pub mod benchmark;
 pub mod experiment;
+pub mod jobs;
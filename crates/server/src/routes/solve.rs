@@ -1,8 +1,88 @@
 use crate::api_types::{SolveRequest, SolveResponse};
+use crate::error::ApiError;
 use crate::state::AppState;
-use axum::{extract::State, http::StatusCode, Json};
-use core::algorithms::{greedy_solve, sa_solve};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::{extract::State, Json};
+use core::algorithms::simulated_annealing::{self, SaConfig, SaEvent};
+use core::algorithms::solver::{check_instance, DynSolver, StopToken};
+use core::models::constraints::SolveParams;
+use core::models::itinerary::Itinerary;
+use core::models::travel::TravelTimeProvider;
+use futures_util::stream::{self, Stream};
+use serde_json::Value;
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Cancels the solve if the handler is dropped, e.g. because the client
+/// disconnected.
+struct CancelOnDrop(StopToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// A validated solve request, ready to run on a blocking thread.
+pub struct PreparedSolve {
+    state: Arc<AppState>,
+    dataset: String,
+    solver: Arc<dyn DynSolver>,
+    params: SolveParams,
+    config: Option<Value>,
+    travel: Arc<dyn TravelTimeProvider>,
+    time_limit: Option<Duration>,
+}
+
+impl PreparedSolve {
+    pub fn new(state: &Arc<AppState>, req: SolveRequest) -> Result<Self, ApiError> {
+        let attractions = state
+            .datasets
+            .get(&req.dataset.name)
+            .ok_or_else(|| ApiError::UnknownDataset(req.dataset.name.clone()))?;
+        let solver = state
+            .solvers
+            .get(&req.algorithm)
+            .ok_or_else(|| ApiError::UnknownAlgorithm(req.algorithm.clone()))?;
+        let params = state.prepare_params(req.params)?;
+        check_instance(solver.as_ref(), attractions, &params)?;
+
+        Ok(Self {
+            state: state.clone(),
+            travel: state.travel_for(&req.dataset.name),
+            dataset: req.dataset.name,
+            solver,
+            params,
+            config: req.config,
+            time_limit: req.time_limit_ms.map(Duration::from_millis),
+        })
+    }
+
+    /// Solve, stopping at the time limit or when `stop` fires.
+    pub fn run(self, stop: &StopToken) -> Result<Itinerary, core::Error> {
+        let stop = self.stop_token(stop);
+        let attractions = &self.state.datasets[&self.dataset];
+        self.solver
+            .solve(attractions, &self.params, self.config.as_ref(), 42, self.travel.as_ref(), &stop)
+    }
+
+    /// Like `run`, for simulated annealing only, passing its progress to
+    /// `observe`.
+    fn run_sa_observed(self, config: &SaConfig, stop: &StopToken, observe: &mut dyn FnMut(SaEvent)) -> Itinerary {
+        let stop = self.stop_token(stop);
+        let attractions = &self.state.datasets[&self.dataset];
+        simulated_annealing::solve_observed(attractions, &self.params, config, 42, self.travel.as_ref(), &stop, observe)
+    }
+
+    fn stop_token(&self, stop: &StopToken) -> StopToken {
+        match self.time_limit {
+            Some(limit) => stop.with_time_limit(limit),
+            None => stop.clone(),
+        }
+    }
+}
 
 #[utoipa::path(
     post,
@@ -10,24 +90,81 @@ use std::sync::Arc;
     request_body = SolveRequest,
     responses(
         (status = 200, description = "Solved itinerary", body = SolveResponse),
-        (status = 400, description = "Invalid request"),
-        (status = 404, description = "Dataset not found")
+        (status = 400, description = "Unknown algorithm or invalid config", body = ErrorBody),
+        (status = 404, description = "Dataset not found", body = ErrorBody),
+        (status = 422, description = "Unusable parameters or dataset too large for the algorithm", body = ErrorBody)
     )
 )]
 pub async fn solve(
     State(state): State<Arc<AppState>>,
     Json(req): Json<SolveRequest>,
-) -> Result<Json<SolveResponse>, StatusCode> {
-    let attractions = state
-        .datasets
-        .get(&req.dataset.name)
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    let itinerary = match req.algorithm.as_str() {
-        "greedy" => greedy_solve(attractions, &req.params, 42),
-        "simulated_annealing" => sa_solve(attractions, &req.params, 42),
-        _ => return Err(StatusCode::BAD_REQUEST),
-    };
+) -> Result<Json<SolveResponse>, ApiError> {
+    let prepared = PreparedSolve::new(&state, req)?;
+    let stop = StopToken::new();
+    let _guard = CancelOnDrop(stop.clone());
+
+    let itinerary = tokio::task::spawn_blocking(move || prepared.run(&stop)).await??;
 
     Ok(Json(SolveResponse { itinerary }))
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/solve/stream",
+    request_body = SolveRequest,
+    responses(
+        (status = 200, description = "Server-sent events: `convergence` (ConvergencePoint) every 50 \
+            iterations, `improved` (Itinerary) when the best plan improves, then `done` (SolveResponse). \
+            Closing the connection stops the solve.", content_type = "text/event-stream"),
+        (status = 400, description = "Algorithm is not simulated_annealing or invalid config", body = ErrorBody),
+        (status = 404, description = "Dataset not found", body = ErrorBody),
+        (status = 422, description = "Unusable parameters", body = ErrorBody)
+    )
+)]
+pub async fn solve_stream(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<SolveRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    if req.algorithm != "simulated_annealing" {
+        return Err(ApiError::UnsupportedAlgorithm {
+            algorithm: req.algorithm,
+            reason: "only simulated_annealing streams its progress",
+        });
+    }
+    let config: SaConfig = match &req.config {
+        Some(value) if !value.is_null() => {
+            serde_json::from_value(value.clone()).map_err(|e| core::Error::InvalidConfig {
+                solver: req.algorithm.clone(),
+                message: e.to_string(),
+            })?
+        }
+        _ => SaConfig::default(),
+    };
+    let prepared = PreparedSolve::new(&state, req)?;
+    let stop = StopToken::new();
+    let guard = CancelOnDrop(stop.clone());
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::task::spawn_blocking(move || {
+        let itinerary = prepared.run_sa_observed(&config, &stop, &mut |event| {
+            let event = match event {
+                SaEvent::Convergence(point) => Event::default().event("convergence").json_data(point),
+                SaEvent::Improved(itinerary) => Event::default().event("improved").json_data(itinerary),
+            };
+            if let Ok(event) = event {
+                if tx.send(event).is_err() {
+                    stop.cancel();
+                }
+            }
+        });
+        if let Ok(event) = Event::default().event("done").json_data(SolveResponse { itinerary }) {
+            let _ = tx.send(event);
+        }
+    });
+
+    // The guard lives as long as the stream, so a disconnect stops the solve
+    let events = stream::unfold((rx, guard), |(mut rx, guard)| async move {
+        rx.recv().await.map(|event| (Ok(event), (rx, guard)))
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
@@ -1,14 +1,34 @@
-use core::models::attraction::Attraction;
+use crate::error::ApiError;
+use crate::jobs::JobQueue;
+use core::algorithms::solver::{DynSolver, SolverRegistry};
+use core::models::attraction::{load_dataset, Attraction};
+use core::models::calendar::HolidayCalendar;
+use core::models::constraints::SolveParams;
+use core::models::travel::{load_travel_model, HaversineProvider, TravelTimeProvider};
 use std::collections::HashMap;
 use std::fs;
 use std::env;
+use std::sync::Arc;
 
 pub struct AppState {
     pub datasets: HashMap<String, Vec<Attraction>>,
+    /// Travel models keyed by dataset name, built from
+    /// `data/matrices/<dataset>.json` and `data/traffic/<dataset>.json`.
+    pub travel_models: HashMap<String, Arc<dyn TravelTimeProvider>>,
+    default_travel: Arc<dyn TravelTimeProvider>,
+    /// Public holidays from `data/holidays.json`.
+    pub holidays: HolidayCalendar,
+    pub solvers: SolverRegistry,
+    /// Background solves and experiments; `MAX_CONCURRENT_JOBS` sets how
+    /// many run at once (default: half the CPUs).
+    pub jobs: Arc<JobQueue>,
 }
 
 impl AppState {
-    pub fn new() -> Self {
+    /// Load every dataset under `data/datasets` with its travel model, and
+    /// the holiday calendar. A missing directory leaves the server without
+    /// datasets; a file that does not load is an error.
+    pub fn new() -> Result<Self, core::Error> {
         let mut datasets = HashMap::new();
         
         // Get the project root - go up from the executable location
@@ -22,48 +42,142 @@ impl AppState {
         if !dataset_dir.exists() {
             eprintln!("❌ Dataset directory does not exist: {}", dataset_dir.display());
             eprintln!("Run from project root: cargo run -p server");
-            return Self { datasets: HashMap::new() };
+            return Ok(Self {
+                datasets: HashMap::new(),
+                travel_models: HashMap::new(),
+                default_travel: Arc::new(HaversineProvider::default()),
+                holidays: HolidayCalendar::default(),
+                solvers: SolverRegistry::default(),
+                jobs: Arc::new(JobQueue::new(Self::max_concurrent_jobs())),
+            });
         }
         
         // Load all JSON files from datasets directory
-        match fs::read_dir(&dataset_dir) {
-            Ok(entries) => {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                        let name = path.file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("unknown")
-                            .to_string();
-                        
-                        println!("Trying to load: {}", path.display());
-                        
-                        match fs::read_to_string(&path) {
-                            Ok(content) => {
-                                match serde_json::from_str::<Vec<Attraction>>(&content) {
-                                    Ok(attractions) => {
-                                        println!("✓ Loaded dataset: {} ({} attractions)", name, attractions.len());
-                                        datasets.insert(name, attractions);
-                                    }
-                                    Err(e) => {
-                                        eprintln!("❌ Failed to parse {}: {}", path.display(), e);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("❌ Failed to read {}: {}", path.display(), e);
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("❌ Failed to read directory {}: {}", dataset_dir.display(), e);
+        let entries = fs::read_dir(&dataset_dir).map_err(|e| core::Error::Dataset {
+            path: dataset_dir.display().to_string(),
+            line: None,
+            column: None,
+            message: format!("Failed to read directory: {}", e),
+        })?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                let name = path.file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                
+                println!("Trying to load: {}", path.display());
+                let attractions = load_dataset(&path)?;
+                println!("✓ Loaded dataset: {} ({} attractions)", name, attractions.len());
+                datasets.insert(name, attractions);
             }
         }
         
         println!("Total datasets loaded: {}", datasets.len());
         
-        Self { datasets }
+        let travel_models = Self::load_travel_models(&dataset_dir, &datasets)?;
+        let holidays = Self::load_holidays(&dataset_dir)?;
+        
+        Ok(Self {
+            datasets,
+            travel_models,
+            default_travel: Arc::new(HaversineProvider::default()),
+            holidays,
+            solvers: SolverRegistry::default(),
+            jobs: Arc::new(JobQueue::new(Self::max_concurrent_jobs())),
+        })
+    }
+
+    fn max_concurrent_jobs() -> usize {
+        env::var("MAX_CONCURRENT_JOBS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| {
+                std::thread::available_parallelism().map_or(1, |n| n.get() / 2).max(1)
+            })
+    }
+
+    /// Request parameters with the calendar's holidays for the trip dates
+    /// filled in, or every problem `SolveParams::validate` finds.
+    pub fn prepare_params(&self, mut params: SolveParams) -> Result<SolveParams, core::Error> {
+        params.validate().map_err(core::Error::InvalidParams)?;
+        self.holidays.apply_to(&mut params);
+        Ok(params)
+    }
+
+    /// Solvers named in a request, or by default every registered one
+    /// (exact ones only if `include_exact`), leaving out those that do not
+    /// handle `n_attractions`.
+    pub fn select_solvers(
+        &self,
+        names: Option<&[String]>,
+        n_attractions: usize,
+        include_exact: bool,
+    ) -> Result<Vec<Arc<dyn DynSolver>>, ApiError> {
+        let selected: Vec<Arc<dyn DynSolver>> = match names {
+            Some(names) => names
+                .iter()
+                .map(|name| self.solvers.get(name).ok_or_else(|| ApiError::UnknownAlgorithm(name.clone())))
+                .collect::<Result<_, _>>()?,
+            None => self
+                .solvers
+                .iter()
+                .filter(|solver| include_exact || !solver.capabilities().exact)
+                .cloned()
+                .collect(),
+        };
+        Ok(selected
+            .into_iter()
+            .filter(|solver| solver.capabilities().supports(n_attractions))
+            .collect())
+    }
+
+    fn load_holidays(dataset_dir: &std::path::Path) -> Result<HolidayCalendar, core::Error> {
+        let path = match dataset_dir.parent() {
+            Some(data_dir) => data_dir.join("holidays.json"),
+            None => return Ok(HolidayCalendar::default()),
+        };
+        if !path.exists() {
+            return Ok(HolidayCalendar::default());
+        }
+        
+        let calendar = HolidayCalendar::from_json_file(&path)?;
+        println!("✓ Loaded holiday calendar ({} holidays)", calendar.holidays.len());
+        Ok(calendar)
+    }
+
+    /// Travel model for a dataset: its matrix and traffic profile if any
+    /// were loaded, otherwise straight-line estimates.
+    pub fn travel_for(&self, dataset: &str) -> Arc<dyn TravelTimeProvider> {
+        self.travel_models
+            .get(dataset)
+            .cloned()
+            .unwrap_or_else(|| self.default_travel.clone())
+    }
+
+    fn load_travel_models(
+        dataset_dir: &std::path::Path,
+        datasets: &HashMap<String, Vec<Attraction>>,
+    ) -> Result<HashMap<String, Arc<dyn TravelTimeProvider>>, core::Error> {
+        let mut models: HashMap<String, Arc<dyn TravelTimeProvider>> = HashMap::new();
+        let data_dir = match dataset_dir.parent() {
+            Some(data_dir) => data_dir,
+            None => return Ok(models),
+        };
+        
+        for (name, attractions) in datasets {
+            let matrix_path = data_dir.join("matrices").join(format!("{}.json", name));
+            let traffic_path = data_dir.join("traffic").join(format!("{}.json", name));
+            if !matrix_path.exists() && !traffic_path.exists() {
+                continue;
+            }
+            
+            let provider = load_travel_model(data_dir, name, attractions)?;
+            println!("✓ Loaded travel model for {}", name);
+            models.insert(name.clone(), Arc::from(provider));
+        }
+        
+        Ok(models)
     }
 }
@@ -0,0 +1,198 @@
+//! Compare two result files cell by cell and flag regressions.
+
+use crate::results::{Cell, SuiteResults};
+use core::utils::experiments::compare_runs;
+use core::utils::statistics::WilcoxonResult;
+
+pub struct Thresholds {
+    /// Smallest drop in mean satisfaction, in percent, worth flagging.
+    pub min_drop_pct: f64,
+    /// Significance level for the paired Wilcoxon test.
+    pub alpha: f64,
+}
+
+pub struct Comparison<'a> {
+    pub baseline: &'a Cell,
+    pub candidate: &'a Cell,
+    pub change_pct: f64,
+    pub test: WilcoxonResult,
+    /// Why this cell counts as a regression, if it does.
+    pub regression: Option<String>,
+}
+
+pub struct Report<'a> {
+    pub comparisons: Vec<Comparison<'a>>,
+    /// Cells present in only one of the files, as `dataset/params/algorithm`.
+    pub only_in_baseline: Vec<String>,
+    pub only_in_candidate: Vec<String>,
+}
+
+impl Report<'_> {
+    pub fn regressions(&self) -> usize {
+        self.comparisons.iter().filter(|c| c.regression.is_some()).count()
+    }
+}
+
+/// A cell regresses when it has fewer valid runs, or when its mean
+/// satisfaction drops by at least `min_drop_pct` and the paired test on
+/// the shared seeds is significant.
+pub fn compare<'a>(baseline: &'a SuiteResults, candidate: &'a SuiteResults, thresholds: &Thresholds) -> Report<'a> {
+    let mut comparisons = Vec::new();
+    let mut only_in_baseline = Vec::new();
+    for base in &baseline.cells {
+        let Some(cand) = candidate.cells.iter().find(|cell| cell.key() == base.key()) else {
+            only_in_baseline.push(describe(base));
+            continue;
+        };
+
+        let change_pct = if base.mean_satisfaction > 0.0 {
+            (cand.mean_satisfaction - base.mean_satisfaction) / base.mean_satisfaction * 100.0
+        } else {
+            0.0
+        };
+        let test = compare_runs(&cand.runs, &base.runs);
+        let regression = if cand.valid_runs < base.valid_runs {
+            Some(format!("{} invalid runs, was {}", cand.runs.len() - cand.valid_runs, base.runs.len() - base.valid_runs))
+        } else if -change_pct >= thresholds.min_drop_pct && test.p_value < thresholds.alpha && test.w_minus > test.w_plus {
+            Some(format!("satisfaction {:+.2}% (p = {:.4})", change_pct, test.p_value))
+        } else {
+            None
+        };
+        comparisons.push(Comparison { baseline: base, candidate: cand, change_pct, test, regression });
+    }
+
+    let only_in_candidate = candidate
+        .cells
+        .iter()
+        .filter(|cell| !baseline.cells.iter().any(|base| base.key() == cell.key()))
+        .map(describe)
+        .collect();
+
+    Report { comparisons, only_in_baseline, only_in_candidate }
+}
+
+pub fn print_report(baseline: &SuiteResults, candidate: &SuiteResults, report: &Report) {
+    let commit = |results: &SuiteResults| {
+        let commit = results.git_commit.as_deref().map_or("unknown", |c| &c[..c.len().min(10)]);
+        format!("{}{}", commit, if results.git_dirty { " (dirty)" } else { "" })
+    };
+    println!("Baseline:  {} @ {}", baseline.suite, commit(baseline));
+    println!("Candidate: {} @ {}", candidate.suite, commit(candidate));
+    println!();
+    println!(
+        "{:<10} {:<30} {:<22} {:>10} {:>10} {:>8} {:>8}  status",
+        "dataset", "params", "algorithm", "baseline", "candidate", "change", "p"
+    );
+    for c in &report.comparisons {
+        let status = match &c.regression {
+            Some(reason) => format!("❌ {}", reason),
+            None if c.test.p_value < 0.05 && c.change_pct > 0.0 => "✓ improved".to_string(),
+            None => "✓".to_string(),
+        };
+        let config_note = if c.baseline.config != c.candidate.config { " (config changed)" } else { "" };
+        println!(
+            "{:<10} {:<30} {:<22} {:>10.4} {:>10.4} {:>+7.2}% {:>8.4}  {}{}",
+            c.baseline.dataset,
+            c.baseline.params_id,
+            c.baseline.algorithm,
+            c.baseline.mean_satisfaction,
+            c.candidate.mean_satisfaction,
+            c.change_pct,
+            c.test.p_value,
+            status,
+            config_note,
+        );
+    }
+    for cell in &report.only_in_baseline {
+        println!("⚠️  Only in baseline: {}", cell);
+    }
+    for cell in &report.only_in_candidate {
+        println!("⚠️  Only in candidate: {}", cell);
+    }
+}
+
+fn describe(cell: &Cell) -> String {
+    format!("{}/{}/{}", cell.dataset, cell.params_id, cell.algorithm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::models::constraints::SolveParams;
+    use core::utils::experiments::RunOutcome;
+    use core::utils::statistics::{mean, Summary};
+    use serde_json::Value;
+
+    fn cell(algorithm: &str, satisfactions: &[f64], invalid: usize) -> Cell {
+        let runs: Vec<RunOutcome> = satisfactions
+            .iter()
+            .enumerate()
+            .map(|(i, &satisfaction)| RunOutcome {
+                seed: i as u64,
+                satisfaction,
+                ms: 1.0,
+                error: (i < invalid).then(|| "Total cost exceeds budget".to_string()),
+            })
+            .collect();
+        Cell {
+            dataset: "small".to_string(),
+            params_id: "default".to_string(),
+            params: SolveParams::default(),
+            algorithm: algorithm.to_string(),
+            solver: algorithm.to_string(),
+            config: Value::Null,
+            mean_satisfaction: mean(satisfactions),
+            std_satisfaction: 0.0,
+            mean_ms: 1.0,
+            valid_runs: satisfactions.len() - invalid,
+            satisfaction: Summary::of(satisfactions),
+            runs,
+        }
+    }
+
+    fn results(cells: Vec<Cell>) -> SuiteResults {
+        SuiteResults {
+            suite: "test".to_string(),
+            git_commit: None,
+            git_dirty: false,
+            created_at: 0,
+            suite_file: Value::Null,
+            cells,
+        }
+    }
+
+    #[test]
+    fn test_flags_significant_drops_and_new_invalid_runs() {
+        let base: Vec<f64> = (0..10).map(|i| 10.0 + i as f64 * 0.1).collect();
+        let worse: Vec<f64> = base.iter().map(|s| s * 0.9).collect();
+        let noisy: Vec<f64> = base.iter().enumerate().map(|(i, s)| if i % 2 == 0 { s * 0.95 } else { s * 1.04 }).collect();
+
+        let baseline = results(vec![
+            cell("sa", &base, 0),
+            cell("alns", &base, 0),
+            cell("ga", &base, 0),
+            cell("greedy", &base, 0),
+            cell("gone", &base, 0),
+        ]);
+        let candidate = results(vec![
+            cell("sa", &worse, 0),
+            cell("alns", &noisy, 0),
+            cell("ga", &base, 2),
+            cell("greedy", &base, 0),
+            cell("new", &base, 0),
+        ]);
+        let thresholds = Thresholds { min_drop_pct: 1.0, alpha: 0.05 };
+        let report = compare(&baseline, &candidate, &thresholds);
+
+        let flagged: Vec<&str> = report
+            .comparisons
+            .iter()
+            .filter(|c| c.regression.is_some())
+            .map(|c| c.baseline.algorithm.as_str())
+            .collect();
+        assert_eq!(flagged, vec!["sa", "ga"]);
+        assert_eq!(report.regressions(), 2);
+        assert_eq!(report.only_in_baseline, vec!["small/default/gone"]);
+        assert_eq!(report.only_in_candidate, vec!["small/default/new"]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Experiment suites: run a suite file (datasets × parameter grid ×
+//! algorithms × seeds) into reproducible result files, and compare two
+//! result files for regressions.
+
+mod compare;
+mod results;
+mod suite;
+
+use compare::Thresholds;
+use core::algorithms::solver::{SolverRegistry, StopToken};
+use core::models::attraction::{self, Attraction};
+use core::models::calendar::HolidayCalendar;
+use core::models::travel::load_travel_model;
+use core::utils::experiments::run_experiments_on_seeds;
+use core::utils::parallel::default_threads;
+use results::{Cell, SuiteResults};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use suite::Suite;
+
+const USAGE: &str = "Usage:
+  cargo run --release -p suite -- run SUITE.json [--out DIR] [--threads N]
+  cargo run --release -p suite -- compare BASELINE.json CANDIDATE.json [--min-drop PCT] [--alpha P]
+
+run      writes DIR/<suite>.json and DIR/<suite>.csv (default DIR: results)
+compare  exits with 1 if any cell regressed: fewer valid runs, or mean
+         satisfaction down by at least PCT percent (default: 1) with a
+         paired Wilcoxon p-value below P (default: 0.05)";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let outcome = match args[0].as_str() {
+        "run" => parse_run(&args[1..]).and_then(|(suite, out, threads)| run(&suite, &out, threads)),
+        "compare" => parse_compare(&args[1..]).and_then(|(baseline, candidate, thresholds)| {
+            compare(&baseline, &candidate, &thresholds)
+        }),
+        other => Err(format!("Unknown command '{}'", other)),
+    };
+
+    match outcome {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("❌ {}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn run(suite_path: &Path, out: &Path, threads: usize) -> Result<bool, String> {
+    let suite = Suite::load(suite_path)?;
+    let registry = SolverRegistry::default();
+    let seeds = suite.seeds.resolve();
+    let grid = suite.param_grid()?;
+    for point in &grid {
+        point.params.validate().map_err(|problems| {
+            let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+            format!("Invalid parameters for {}: {}", point.id, problems.join("; "))
+        })?;
+    }
+    for spec in &suite.algorithms {
+        let solver = registry.get(spec.algorithm()).ok_or_else(|| {
+            format!("Unknown algorithm '{}', expected one of {:?}", spec.algorithm(), registry.names())
+        })?;
+        solver.resolve_config(spec.config()).map_err(|e| e.to_string())?;
+    }
+    // No calendar is fine, but a broken one must not be silently skipped
+    let holidays_path = Path::new("data/holidays.json");
+    let calendar = if holidays_path.exists() {
+        Some(HolidayCalendar::from_json_file(holidays_path).map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    println!(
+        "Running suite '{}': {} datasets × {} params × {} algorithms × {} seeds",
+        suite.name,
+        suite.datasets.len(),
+        grid.len(),
+        suite.algorithms.len(),
+        seeds.len(),
+    );
+
+    let mut cells = Vec::new();
+    for dataset in &suite.datasets {
+        let attractions = load_dataset(dataset)?;
+        let travel = load_travel_model(Path::new("data"), dataset, &attractions).map_err(|e| e.to_string())?;
+        for point in &grid {
+            let mut params = point.params.clone();
+            if let Some(calendar) = &calendar {
+                calendar.apply_to(&mut params);
+            }
+            for spec in &suite.algorithms {
+                let solver = registry.get(spec.algorithm()).expect("checked above");
+                if !solver.capabilities().supports(attractions.len()) {
+                    println!("  skipping {} on {} ({} attractions)", spec.label(), dataset, attractions.len());
+                    continue;
+                }
+                let config = solver.resolve_config(spec.config()).map_err(|e| e.to_string())?;
+                let stats = run_experiments_on_seeds(&attractions, &params, travel.as_ref(), &seeds, threads, |attrs, params, seed| {
+                    solver
+                        .solve(attrs, params, Some(&config), seed, travel.as_ref(), &StopToken::new())
+                        .expect("config was resolved")
+                });
+                println!(
+                    "  {:<10} {:<30} {:<22} {:>8.4} ± {:<7.4} {}/{} valid",
+                    dataset,
+                    point.id,
+                    spec.label(),
+                    stats.mean_satisfaction,
+                    stats.std_satisfaction,
+                    stats.valid_runs,
+                    seeds.len(),
+                );
+                cells.push(Cell {
+                    dataset: dataset.clone(),
+                    params_id: point.id.clone(),
+                    params: params.clone(),
+                    algorithm: spec.label().to_string(),
+                    solver: solver.name().to_string(),
+                    config,
+                    mean_satisfaction: stats.mean_satisfaction,
+                    std_satisfaction: stats.std_satisfaction,
+                    mean_ms: stats.mean_ms,
+                    valid_runs: stats.valid_runs,
+                    satisfaction: stats.satisfaction,
+                    runs: stats.runs,
+                });
+            }
+        }
+    }
+
+    let (git_commit, git_dirty) = git_state();
+    let results = SuiteResults {
+        suite: suite.name.clone(),
+        git_commit,
+        git_dirty,
+        created_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        suite_file: serde_json::to_value(&suite).map_err(|e| e.to_string())?,
+        cells,
+    };
+
+    fs::create_dir_all(out).map_err(|e| format!("Failed to create {}: {}", out.display(), e))?;
+    let json_path = out.join(format!("{}.json", suite.name));
+    let csv_path = out.join(format!("{}.csv", suite.name));
+    results.write_json(&json_path)?;
+    results.write_csv(&csv_path)?;
+    println!("✓ Wrote {} and {}", json_path.display(), csv_path.display());
+    Ok(true)
+}
+
+fn compare(baseline_path: &Path, candidate_path: &Path, thresholds: &Thresholds) -> Result<bool, String> {
+    let baseline = SuiteResults::load(baseline_path)?;
+    let candidate = SuiteResults::load(candidate_path)?;
+    let report = compare::compare(&baseline, &candidate, thresholds);
+    compare::print_report(&baseline, &candidate, &report);
+
+    println!();
+    let regressions = report.regressions();
+    if regressions == 0 {
+        println!("✓ No regressions in {} cells", report.comparisons.len());
+        Ok(true)
+    } else {
+        println!("❌ {} of {} cells regressed", regressions, report.comparisons.len());
+        Ok(false)
+    }
+}
+
+fn load_dataset(name: &str) -> Result<Vec<Attraction>, String> {
+    let path = Path::new("data/datasets").join(format!("{}.json", name));
+    if !path.exists() {
+        return Err(format!("{} does not exist (run from the project root)", path.display()));
+    }
+    attraction::load_dataset(&path).map_err(|e| e.to_string())
+}
+
+/// The current commit and whether the tree has uncommitted changes.
+fn git_state() -> (Option<String>, bool) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "HEAD"]);
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+    (commit, dirty)
+}
+
+fn parse_run(args: &[String]) -> Result<(PathBuf, PathBuf, usize), String> {
+    let mut suite = None;
+    let mut out = PathBuf::from("results");
+    let mut threads = default_threads();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = PathBuf::from(args.next().ok_or("Missing value for --out")?),
+            "--threads" => {
+                let value = args.next().ok_or("Missing value for --threads")?;
+                threads = value.parse().map_err(|_| format!("Invalid number for --threads: {}", value))?;
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            path if suite.is_none() => suite = Some(PathBuf::from(path)),
+            extra => return Err(format!("Unexpected argument {}", extra)),
+        }
+    }
+    Ok((suite.ok_or("Missing suite file")?, out, threads))
+}
+
+fn parse_compare(args: &[String]) -> Result<(PathBuf, PathBuf, Thresholds), String> {
+    let mut files = Vec::new();
+    let mut thresholds = Thresholds { min_drop_pct: 1.0, alpha: 0.05 };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut number = |flag: &str| -> Result<f64, String> {
+            let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+            value.parse().map_err(|_| format!("Invalid number for {}: {}", flag, value))
+        };
+        match arg.as_str() {
+            "--min-drop" => thresholds.min_drop_pct = number("--min-drop")?,
+            "--alpha" => thresholds.alpha = number("--alpha")?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            path => files.push(PathBuf::from(path)),
+        }
+    }
+    match <[PathBuf; 2]>::try_from(files) {
+        Ok([baseline, candidate]) => Ok((baseline, candidate, thresholds)),
+        Err(_) => Err("Expected a baseline and a candidate result file".to_string()),
+    }
+}
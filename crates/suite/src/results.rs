@@ -0,0 +1,97 @@
+//! Result files written by `suite run` and read back by `suite compare`.
+
+use core::models::constraints::SolveParams;
+use core::utils::experiments::RunOutcome;
+use core::utils::statistics::Summary;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuiteResults {
+    pub suite: String,
+    /// `git rev-parse HEAD` when the suite ran, if inside a repository.
+    pub git_commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub git_dirty: bool,
+    /// Seconds since the Unix epoch.
+    pub created_at: u64,
+    /// The suite file as run.
+    pub suite_file: Value,
+    pub cells: Vec<Cell>,
+}
+
+/// One dataset × params × algorithm combination over every seed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cell {
+    pub dataset: String,
+    pub params_id: String,
+    pub params: SolveParams,
+    /// The suite's label for the solver and settings.
+    pub algorithm: String,
+    pub solver: String,
+    /// Every setting the solver ran with, defaults included.
+    pub config: Value,
+    pub mean_satisfaction: f64,
+    pub std_satisfaction: f64,
+    pub mean_ms: f64,
+    pub valid_runs: usize,
+    pub satisfaction: Summary,
+    pub runs: Vec<RunOutcome>,
+}
+
+impl Cell {
+    /// What identifies the same cell across result files.
+    pub fn key(&self) -> (&str, &str, &str) {
+        (&self.dataset, &self.params_id, &self.algorithm)
+    }
+}
+
+impl SuiteResults {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// One row per run, with the commit, config and params repeated so
+    /// rows stand on their own.
+    pub fn write_csv(&self, path: &Path) -> Result<(), String> {
+        let mut csv = String::from("git_commit,dataset,params_id,algorithm,solver,seed,satisfaction,ms,valid,error,config,params\n");
+        let commit = self.git_commit.as_deref().unwrap_or("");
+        for cell in &self.cells {
+            let params = serde_json::to_string(&cell.params).map_err(|e| e.to_string())?;
+            for run in &cell.runs {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{:.6},{:.1},{},{},{},{}\n",
+                    commit,
+                    cell.dataset,
+                    quote(&cell.params_id),
+                    quote(&cell.algorithm),
+                    cell.solver,
+                    run.seed,
+                    run.satisfaction,
+                    run.ms,
+                    run.error.is_none(),
+                    quote(run.error.as_deref().unwrap_or("")),
+                    quote(&cell.config.to_string()),
+                    quote(&params),
+                ));
+            }
+        }
+        fs::write(path, csv).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,207 @@
+//! Suite files: the datasets, parameter grid, solvers and seeds to run.
+
+use core::models::constraints::SolveParams;
+use core::utils::experiments::experiment_seeds;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    pub name: String,
+    /// Names from `data/datasets`.
+    pub datasets: Vec<String>,
+    /// `SolveParams` fields shared by every cell; the rest keep their
+    /// defaults.
+    #[serde(default)]
+    pub params: Map<String, Value>,
+    /// `SolveParams` fields to vary; every combination is run.
+    #[serde(default)]
+    pub grid: BTreeMap<String, Vec<Value>>,
+    pub algorithms: Vec<AlgorithmSpec>,
+    pub seeds: Seeds,
+}
+
+/// A solver name, or a solver with settings and a label to tell it apart
+/// from other settings of the same solver.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AlgorithmSpec {
+    Name(String),
+    Configured {
+        algorithm: String,
+        #[serde(default)]
+        config: Option<Value>,
+        #[serde(default)]
+        label: Option<String>,
+    },
+}
+
+impl AlgorithmSpec {
+    pub fn algorithm(&self) -> &str {
+        match self {
+            AlgorithmSpec::Name(name) => name,
+            AlgorithmSpec::Configured { algorithm, .. } => algorithm,
+        }
+    }
+
+    pub fn config(&self) -> Option<&Value> {
+        match self {
+            AlgorithmSpec::Name(_) => None,
+            AlgorithmSpec::Configured { config, .. } => config.as_ref(),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            AlgorithmSpec::Configured { label: Some(label), .. } => label,
+            _ => self.algorithm(),
+        }
+    }
+}
+
+/// Explicit seeds, or `{"count": n}` for the seeds `/api/experiment` uses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Seeds {
+    List(Vec<u64>),
+    Count { count: usize },
+}
+
+impl Seeds {
+    pub fn resolve(&self) -> Vec<u64> {
+        match self {
+            Seeds::List(seeds) => seeds.clone(),
+            Seeds::Count { count } => experiment_seeds(*count),
+        }
+    }
+}
+
+/// One point of the parameter grid.
+pub struct ParamsCell {
+    /// `field=value` pairs of the grid, e.g. `num_days=2,total_budget=50`,
+    /// or `default` without a grid.
+    pub id: String,
+    pub params: SolveParams,
+}
+
+impl Suite {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let suite: Suite =
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+        let mut labels: Vec<&str> = suite.algorithms.iter().map(AlgorithmSpec::label).collect();
+        labels.sort();
+        if let Some(pair) = labels.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(format!("Algorithm label '{}' is used twice; give each a distinct label", pair[0]));
+        }
+        if suite.seeds.resolve().is_empty() {
+            return Err("The suite has no seeds".to_string());
+        }
+        Ok(suite)
+    }
+
+    /// Every combination of the grid over the shared params, in a fixed
+    /// order.
+    pub fn param_grid(&self) -> Result<Vec<ParamsCell>, String> {
+        // `SolveParams` ignores unknown fields, so a misspelt one would
+        // silently run the defaults under a different cell id
+        let defaults = match serde_json::to_value(SolveParams::default()) {
+            Ok(Value::Object(fields)) => fields,
+            _ => Map::new(),
+        };
+        if let Some(field) = self.params.keys().chain(self.grid.keys()).find(|field| !defaults.contains_key(*field)) {
+            return Err(format!("'{}' is not a SolveParams field", field));
+        }
+
+        let mut combinations: Vec<Vec<(&String, &Value)>> = vec![Vec::new()];
+        for (field, values) in &self.grid {
+            if values.is_empty() {
+                return Err(format!("Grid field '{}' has no values", field));
+            }
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combination| {
+                    values.iter().map(move |value| {
+                        let mut next = combination.clone();
+                        next.push((field, value));
+                        next
+                    })
+                })
+                .collect();
+        }
+
+        combinations
+            .into_iter()
+            .map(|combination| {
+                let mut fields = defaults.clone();
+                fields.extend(self.params.clone());
+                for (field, value) in &combination {
+                    fields.insert(field.to_string(), (*value).clone());
+                }
+                let params: SolveParams = serde_json::from_value(Value::Object(fields))
+                    .map_err(|e| format!("Invalid params in the grid: {}", e))?;
+
+                let id = if combination.is_empty() {
+                    "default".to_string()
+                } else {
+                    combination
+                        .iter()
+                        .map(|(field, value)| format!("{}={}", field, value))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                };
+                Ok(ParamsCell { id, params })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_grid_expands_every_combination() {
+        let suite: Suite = serde_json::from_value(json!({
+            "name": "grid",
+            "datasets": ["small"],
+            "params": { "total_budget": 80.0 },
+            "grid": { "num_days": [1, 2, 3], "start_time": [480, 600] },
+            "algorithms": ["greedy", { "algorithm": "simulated_annealing", "config": { "chains": 2 }, "label": "sa2" }],
+            "seeds": { "count": 4 }
+        }))
+        .unwrap();
+
+        let grid = suite.param_grid().unwrap();
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[0].id, "num_days=1,start_time=480");
+        assert_eq!(grid[5].id, "num_days=3,start_time=600");
+        assert!(grid.iter().all(|cell| cell.params.total_budget == 80.0));
+        assert_eq!(grid[1].params.start_time, 600);
+
+        assert_eq!(suite.algorithms[1].label(), "sa2");
+        assert_eq!(suite.algorithms[0].label(), "greedy");
+        assert_eq!(suite.seeds.resolve(), experiment_seeds(4));
+
+        let bad: Suite = serde_json::from_value(json!({
+            "name": "bad", "datasets": [], "grid": { "num_days": ["many"] }, "algorithms": [], "seeds": [1]
+        }))
+        .unwrap();
+        assert!(bad.param_grid().is_err());
+
+        for (params, grid) in [(json!({ "total_budgt": 80.0 }), json!({})), (json!({}), json!({ "num_dys": [1, 2] }))] {
+            let typo: Suite = serde_json::from_value(json!({
+                "name": "typo", "datasets": [], "params": params, "grid": grid, "algorithms": [], "seeds": [1]
+            }))
+            .unwrap();
+            let err = typo.param_grid().err().unwrap();
+            assert!(err.contains("is not a SolveParams field"), "{}", err);
+        }
+    }
+}
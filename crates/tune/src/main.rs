@@ -0,0 +1,460 @@
+//! Hyperparameter tuning for the registered solvers: samples random
+//! configurations and races them on the datasets in `data/datasets`,
+//! halving the field and doubling the runs each round.
+
+mod space;
+
+use core::algorithms::solver::{DynSolver, SolverRegistry, StopToken};
+use core::models::attraction::{load_dataset, Attraction};
+use core::models::calendar::HolidayCalendar;
+use core::models::constraints::SolveParams;
+use core::models::travel::{load_travel_model, TravelTimeProvider};
+use core::utils::experiments::run_experiments;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "Usage: cargo run --release -p tune -- [options]
+
+Options:
+  --algorithm NAME   solver to tune (default: simulated_annealing)
+  --datasets A,B     datasets from data/datasets (default: all)
+  --params FILE      SolveParams JSON (default: SolveParams::default())
+  --candidates N     random configurations, plus the default (default: 16)
+  --runs N           runs per dataset in the first round (default: 2)
+  --max-runs N       cap on runs per dataset (default: 8)
+  --seed N           seed for sampling configurations (default: 1)
+  --out DIR          where to write results (default: tuning)";
+
+/// Sampling gives up after this many tries per requested candidate.
+const MAX_ATTEMPTS_PER_CANDIDATE: usize = 100;
+
+struct Options {
+    algorithm: String,
+    datasets: Option<Vec<String>>,
+    params: Option<PathBuf>,
+    candidates: usize,
+    runs: usize,
+    max_runs: usize,
+    seed: u64,
+    out: PathBuf,
+}
+
+struct Dataset {
+    name: String,
+    attractions: Vec<Attraction>,
+    travel: Box<dyn TravelTimeProvider>,
+    /// Greedy's satisfaction, which scores are relative to.
+    baseline: f64,
+}
+
+struct Candidate {
+    id: usize,
+    config: Value,
+    /// Mean satisfaction relative to greedy, averaged over datasets.
+    score: f64,
+    mean_ms: f64,
+    satisfaction: Vec<f64>,
+    runs: usize,
+    /// Round the candidate dropped out in; `None` for the winner.
+    eliminated: Option<usize>,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(e) = run(&options) {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let registry = SolverRegistry::default();
+    let solver = registry.get(&options.algorithm).ok_or_else(|| {
+        format!("Unknown algorithm '{}', expected one of {:?}", options.algorithm, registry.names())
+    })?;
+    let greedy = registry.get("greedy").ok_or("Greedy solver is not registered")?;
+
+    let mut params = match &options.params {
+        Some(path) => {
+            let content = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?
+        }
+        None => SolveParams::default(),
+    };
+    params.validate().map_err(|problems| {
+        let problems: Vec<String> = problems.iter().map(|p| p.to_string()).collect();
+        format!("Invalid parameters: {}", problems.join("; "))
+    })?;
+    // No calendar is fine, but a broken one must not be silently skipped
+    let holidays_path = Path::new("data/holidays.json");
+    if holidays_path.exists() {
+        HolidayCalendar::from_json_file(holidays_path).map_err(|e| e.to_string())?.apply_to(&mut params);
+    }
+
+    let datasets = load_datasets(options.datasets.as_deref(), solver.as_ref(), greedy.as_ref(), &params)?;
+    println!(
+        "Tuning {} on {} with {} candidates",
+        solver.name(),
+        datasets.iter().map(|d| d.name.as_str()).collect::<Vec<_>>().join(", "),
+        options.candidates + 1,
+    );
+
+    let mut candidates = sample_candidates(solver.as_ref(), options.candidates, options.seed)?;
+
+    let mut alive: Vec<usize> = (0..candidates.len()).collect();
+    let mut runs = options.runs.max(1);
+    let mut round = 1;
+    loop {
+        println!("Round {}: {} candidates, {} runs per dataset", round, alive.len(), runs);
+        for &i in &alive {
+            evaluate(&mut candidates[i], solver.as_ref(), &datasets, &params, runs);
+        }
+        if !eliminate(&mut candidates, &mut alive, round) {
+            break;
+        }
+        runs = (runs * 2).min(options.max_runs.max(1));
+        round += 1;
+    }
+
+    let winner = &candidates[alive[0]];
+    write_results(options, solver.name(), &datasets, &candidates, winner)?;
+    println!();
+    print_table(&datasets, &candidates);
+    println!();
+    println!(
+        "Best: candidate {} (score {:.4}){}",
+        winner.id,
+        winner.score,
+        if winner.id == 0 { " — the default configuration" } else { "" },
+    );
+    println!("{}", serde_json::to_string_pretty(&winner.config).unwrap_or_default());
+    Ok(())
+}
+
+/// The default configuration plus `count` random ones the solver accepts.
+/// The default always takes part, so the winner is at least as good on
+/// this benchmark.
+fn sample_candidates(solver: &dyn DynSolver, count: usize, seed: u64) -> Result<Vec<Candidate>, String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let default = solver.default_config();
+    let mut candidates = vec![candidate(0, default.clone())];
+    let max_attempts = MAX_ATTEMPTS_PER_CANDIDATE * count;
+    let mut attempts = 0;
+    while candidates.len() <= count {
+        if attempts == max_attempts {
+            return Err(format!(
+                "Only {} of {} sampled {} configurations were valid",
+                candidates.len() - 1,
+                attempts,
+                solver.name()
+            ));
+        }
+        attempts += 1;
+        let config = space::sample(solver.name(), &default, &mut rng);
+        // Reject anything the solver's config type does not accept
+        if solver.resolve_config(Some(&config)).is_ok() {
+            candidates.push(candidate(candidates.len(), config));
+        }
+    }
+    Ok(candidates)
+}
+
+/// Rank the `alive` candidates by score and drop the worse half, marking
+/// them as eliminated in `round`. Equal scores go to the faster
+/// configuration. Returns `false` once a single candidate is left.
+fn eliminate(candidates: &mut [Candidate], alive: &mut Vec<usize>, round: usize) -> bool {
+    alive.sort_by(|&a, &b| {
+        candidates[b]
+            .score
+            .total_cmp(&candidates[a].score)
+            .then(candidates[a].mean_ms.total_cmp(&candidates[b].mean_ms))
+    });
+    if alive.len() <= 1 {
+        return false;
+    }
+    let kept = alive.len().div_ceil(2);
+    for &i in &alive[kept..] {
+        candidates[i].eliminated = Some(round);
+    }
+    alive.truncate(kept);
+    true
+}
+
+fn candidate(id: usize, config: Value) -> Candidate {
+    Candidate { id, config, score: 0.0, mean_ms: 0.0, satisfaction: Vec::new(), runs: 0, eliminated: None }
+}
+
+/// Score a configuration over every dataset. A dataset with any invalid
+/// run scores zero.
+fn evaluate(
+    candidate: &mut Candidate,
+    solver: &dyn DynSolver,
+    datasets: &[Dataset],
+    params: &SolveParams,
+    runs: usize,
+) {
+    let mut score = 0.0;
+    let mut ms = 0.0;
+    candidate.satisfaction.clear();
+    for dataset in datasets {
+        let stats = run_experiments(&dataset.attractions, params, dataset.travel.as_ref(), runs, |attrs, params, seed| {
+            solver
+                .solve(attrs, params, Some(&candidate.config), seed, dataset.travel.as_ref(), &StopToken::new())
+                .expect("config was checked when sampled")
+        });
+        if stats.valid_runs == runs && dataset.baseline > 0.0 {
+            score += stats.mean_satisfaction / dataset.baseline;
+        }
+        ms += stats.mean_ms;
+        candidate.satisfaction.push(stats.mean_satisfaction);
+    }
+    candidate.score = score / datasets.len() as f64;
+    candidate.mean_ms = ms / datasets.len() as f64;
+    candidate.runs = runs;
+}
+
+fn load_datasets(
+    names: Option<&[String]>,
+    solver: &dyn DynSolver,
+    greedy: &dyn DynSolver,
+    params: &SolveParams,
+) -> Result<Vec<Dataset>, String> {
+    let dir = Path::new("data/datasets");
+    let mut available: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {} (run from the project root)", dir.display(), e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .collect();
+    available.sort();
+
+    let selected = match names {
+        Some(names) => {
+            if let Some(missing) = names.iter().find(|name| !available.contains(name)) {
+                return Err(format!("Unknown dataset '{}', expected one of {:?}", missing, available));
+            }
+            names.to_vec()
+        }
+        None => available,
+    };
+
+    let mut datasets = Vec::new();
+    for name in selected {
+        let attractions = load_dataset(&dir.join(format!("{}.json", name))).map_err(|e| e.to_string())?;
+        if !solver.capabilities().supports(attractions.len()) {
+            println!("Skipping {} ({} attractions is too many for {})", name, attractions.len(), solver.name());
+            continue;
+        }
+
+        let travel = load_travel_model(Path::new("data"), &name, &attractions).map_err(|e| e.to_string())?;
+        let baseline = greedy.solve_default(&attractions, params, 0, travel.as_ref()).total_satisfaction;
+        datasets.push(Dataset { name, attractions, travel, baseline });
+    }
+
+    if datasets.is_empty() {
+        return Err("No datasets to tune on".to_string());
+    }
+    Ok(datasets)
+}
+
+fn write_results(
+    options: &Options,
+    algorithm: &str,
+    datasets: &[Dataset],
+    candidates: &[Candidate],
+    winner: &Candidate,
+) -> Result<(), String> {
+    fs::create_dir_all(&options.out)
+        .map_err(|e| format!("Failed to create {}: {}", options.out.display(), e))?;
+
+    let best_path = options.out.join(format!("{}.best.json", algorithm));
+    let best = serde_json::to_string_pretty(&winner.config).map_err(|e| e.to_string())?;
+    fs::write(&best_path, best + "\n").map_err(|e| format!("Failed to write {}: {}", best_path.display(), e))?;
+
+    let table_path = options.out.join(format!("{}.results.csv", algorithm));
+    fs::write(&table_path, results_csv(datasets, candidates))
+        .map_err(|e| format!("Failed to write {}: {}", table_path.display(), e))?;
+
+    println!("✓ Wrote {} and {}", best_path.display(), table_path.display());
+    Ok(())
+}
+
+/// One row per candidate, ranked as in `ranked`, with the config as a
+/// quoted JSON column.
+fn results_csv(datasets: &[Dataset], candidates: &[Candidate]) -> String {
+    let mut csv = String::from("candidate,eliminated_in_round,runs,score,mean_ms");
+    for dataset in datasets {
+        csv.push_str(&format!(",{}_satisfaction", dataset.name));
+    }
+    csv.push_str(",config\n");
+    for candidate in ranked(candidates) {
+        csv.push_str(&format!(
+            "{},{},{},{:.6},{:.1}",
+            candidate.id,
+            candidate.eliminated.map_or(String::new(), |r| r.to_string()),
+            candidate.runs,
+            candidate.score,
+            candidate.mean_ms,
+        ));
+        for satisfaction in &candidate.satisfaction {
+            csv.push_str(&format!(",{:.4}", satisfaction));
+        }
+        csv.push_str(&format!(",\"{}\"\n", candidate.config.to_string().replace('"', "\"\"")));
+    }
+    csv
+}
+
+fn print_table(datasets: &[Dataset], candidates: &[Candidate]) {
+    print!("{:>4} {:>6} {:>5} {:>8} {:>9}", "#", "out", "runs", "score", "ms");
+    for dataset in datasets {
+        print!(" {:>12}", dataset.name);
+    }
+    println!();
+    for candidate in ranked(candidates) {
+        let out = candidate.eliminated.map_or("best".to_string(), |r| format!("r{}", r));
+        print!(
+            "{:>4} {:>6} {:>5} {:>8.4} {:>9.1}",
+            candidate.id, out, candidate.runs, candidate.score, candidate.mean_ms
+        );
+        for satisfaction in &candidate.satisfaction {
+            print!(" {:>12.3}", satisfaction);
+        }
+        println!();
+    }
+}
+
+/// Winner first, then by the round reached and score.
+fn ranked(candidates: &[Candidate]) -> Vec<&Candidate> {
+    let mut ranked: Vec<&Candidate> = candidates.iter().collect();
+    ranked.sort_by(|a, b| {
+        let reached = |c: &Candidate| c.eliminated.unwrap_or(usize::MAX);
+        reached(b).cmp(&reached(a)).then(b.score.total_cmp(&a.score))
+    });
+    ranked
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        algorithm: "simulated_annealing".to_string(),
+        datasets: None,
+        params: None,
+        candidates: 16,
+        runs: 2,
+        max_runs: 8,
+        seed: 1,
+        out: PathBuf::from("tuning"),
+    };
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+        let number = |value: &str| value.parse::<usize>().map_err(|_| format!("Invalid number for {}: {}", flag, value));
+        match flag.as_str() {
+            "--algorithm" => options.algorithm = value,
+            "--datasets" => options.datasets = Some(value.split(',').map(str::to_string).collect()),
+            "--params" => options.params = Some(PathBuf::from(value)),
+            "--candidates" => options.candidates = number(&value)?,
+            "--runs" => options.runs = number(&value)?,
+            "--max-runs" => options.max_runs = number(&value)?,
+            "--seed" => options.seed = number(&value)? as u64,
+            "--out" => options.out = PathBuf::from(value),
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    Ok(options)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::models::travel::HaversineProvider;
+    use serde_json::json;
+
+    fn scored(id: usize, score: f64, mean_ms: f64) -> Candidate {
+        Candidate { score, mean_ms, ..candidate(id, json!({ "id": id })) }
+    }
+
+    #[test]
+    fn test_eliminate_keeps_the_better_half() {
+        let mut candidates = vec![
+            scored(0, 0.9, 10.0),
+            scored(1, 1.1, 10.0),
+            scored(2, 1.0, 20.0),
+            scored(3, 1.0, 5.0),
+            scored(4, 0.5, 1.0),
+        ];
+        let mut alive: Vec<usize> = (0..candidates.len()).collect();
+
+        assert!(eliminate(&mut candidates, &mut alive, 1));
+        // Ties on score go to the faster candidate
+        assert_eq!(alive, vec![1, 3, 2]);
+        let out: Vec<Option<usize>> = candidates.iter().map(|c| c.eliminated).collect();
+        assert_eq!(out, vec![Some(1), None, None, None, Some(1)]);
+
+        assert!(eliminate(&mut candidates, &mut alive, 2));
+        assert_eq!(alive, vec![1, 3]);
+        assert_eq!(candidates[2].eliminated, Some(2));
+        assert!(eliminate(&mut candidates, &mut alive, 3));
+        assert_eq!(alive, vec![1]);
+        assert!(!eliminate(&mut candidates, &mut alive, 4));
+        assert_eq!(candidates[1].eliminated, None);
+    }
+
+    #[test]
+    fn test_results_csv_ranks_winner_first() {
+        let datasets: Vec<Dataset> = ["small", "large"]
+            .iter()
+            .map(|name| Dataset {
+                name: name.to_string(),
+                attractions: Vec::new(),
+                travel: Box::new(HaversineProvider::default()),
+                baseline: 1.0,
+            })
+            .collect();
+        let mut loser = scored(0, 2.0, 3.0);
+        loser.eliminated = Some(1);
+        loser.runs = 2;
+        loser.satisfaction = vec![1.5, 2.5];
+        let mut winner = scored(1, 1.0, 12.34);
+        winner.runs = 4;
+        winner.satisfaction = vec![0.5, 1.5];
+
+        let csv = results_csv(&datasets, &[loser, winner]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "candidate,eliminated_in_round,runs,score,mean_ms,small_satisfaction,large_satisfaction,config",
+                r#"1,,4,1.000000,12.3,0.5000,1.5000,"{""id"":1}""#,
+                r#"0,1,2,2.000000,3.0,1.5000,2.5000,"{""id"":0}""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sampled_candidates_start_with_the_default() {
+        let registry = SolverRegistry::default();
+        let solver = registry.get("genetic").unwrap();
+        let candidates = sample_candidates(solver.as_ref(), 3, 7).unwrap();
+
+        assert_eq!(candidates.len(), 4);
+        assert_eq!(candidates[0].config, solver.default_config());
+        assert!(candidates.iter().all(|c| solver.resolve_config(Some(&c.config)).is_ok()));
+    }
+}
@@ -0,0 +1,61 @@
+//! Configuration spaces the tuner samples from.
+
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde_json::{json, Map, Value};
+
+/// A random configuration for `algorithm`. Simulated annealing has a
+/// hand-written space that also picks the cooling schedule; other solvers
+/// get every number in their default config scaled by up to 2x either way.
+pub fn sample(algorithm: &str, default: &Value, rng: &mut StdRng) -> Value {
+    match algorithm {
+        "simulated_annealing" => sample_sa(rng),
+        _ => perturb(default, rng),
+    }
+}
+
+fn sample_sa(rng: &mut StdRng) -> Value {
+    let cooling = match rng.gen_range(0..3) {
+        0 => json!({ "type": "geometric", "rate": rng.gen_range(0.99..0.9995) }),
+        1 => json!({ "type": "linear" }),
+        _ => json!({
+            "type": "adaptive",
+            "rate": rng.gen_range(0.99..0.9995),
+            "reheat_after": rng.gen_range(100..1_000),
+            "reheat_factor": rng.gen_range(2.0..20.0),
+        }),
+    };
+    json!({
+        "initial_temp": log_uniform(rng, 1.0, 1_000.0),
+        "min_temp": log_uniform(rng, 0.001, 1.0),
+        "max_iterations": log_uniform(rng, 2_000.0, 30_000.0).round() as u64,
+        "cooling": cooling,
+    })
+}
+
+fn perturb(value: &Value, rng: &mut StdRng) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), perturb(value, rng)))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Number(n) => {
+            let factor = 2f64.powf(rng.gen_range(-1.0..1.0));
+            if let Some(int) = n.as_u64() {
+                json!(((int as f64 * factor).round() as u64).max(1))
+            } else {
+                let x = n.as_f64().unwrap_or(0.0);
+                // Values in (0, 1) are probabilities or ratios
+                let scaled = if x > 0.0 && x < 1.0 { (x * factor).min(1.0) } else { x * factor };
+                json!(scaled)
+            }
+        }
+        other => other.clone(),
+    }
+}
+
+fn log_uniform(rng: &mut StdRng, low: f64, high: f64) -> f64 {
+    (rng.gen_range(low.ln()..high.ln())).exp()
+}
@@ -1,6 +1,8 @@
-import React, { useState, useEffect } from "react";
+import React, { useState, useEffect, useRef } from "react";
 import { api } from "./api/client";
 import type {
+  AlgorithmInfo,
+  ConvergencePoint,
   DatasetInfo,
   Itinerary,
   SolveParams,
@@ -16,6 +18,7 @@ type TabType = "itinerary" | "benchmark" | "experiment";
 
 function App() {
   const [datasets, setDatasets] = useState<DatasetInfo[]>([]);
+  const [algorithms, setAlgorithms] = useState<AlgorithmInfo[]>([]);
   const [selectedDataset, setSelectedDataset] = useState<string>("small");
   const [selectedAlgorithm, setSelectedAlgorithm] = useState<string>("greedy");
   const [params, setParams] = useState<SolveParams>({
@@ -34,6 +37,7 @@ function App() {
   const [activeTab, setActiveTab] = useState<TabType>("itinerary");
   const [error, setError] = useState<string | null>(null);
   const [pinDropMode, setPinDropMode] = useState<"start" | "end" | null>(null);
+  const streamAbort = useRef<AbortController | null>(null);
 
   useEffect(() => {
     loadDatasets();
@@ -42,9 +46,13 @@ function App() {
   const loadDatasets = async () => {
     try {
       setError(null);
-      const data = await api.getDatasets();
+      const [data, solvers] = await Promise.all([
+        api.getDatasets(),
+        api.getAlgorithms(),
+      ]);
       console.log("Loaded datasets:", data);
       setDatasets(data);
+      setAlgorithms(solvers);
       if (data.length > 0 && !selectedDataset) {
         setSelectedDataset(data[0].name);
       }
@@ -56,7 +64,48 @@ function App() {
     }
   };
 
+  // Simulated annealing streams its progress so the plan and chart update
+  // live, and the user can stop once satisfied
+  const handleSolveStream = async () => {
+    const controller = new AbortController();
+    streamAbort.current = controller;
+    const points: ConvergencePoint[] = [];
+    setLoading(true);
+    setError(null);
+    setItinerary(null);
+    setActiveTab("itinerary");
+    try {
+      const result = await api.solveStream(
+        selectedDataset,
+        params,
+        {
+          onConvergence: (point) => {
+            points.push(point);
+            setItinerary(
+              (prev) => prev && { ...prev, convergence_data: [...points] },
+            );
+          },
+          onImproved: (improved) =>
+            setItinerary({ ...improved, convergence_data: [...points] }),
+        },
+        controller.signal,
+      );
+      if (result) setItinerary(result);
+    } catch (error: any) {
+      console.error("Failed to solve:", error);
+      setError(error.message || "Failed to solve. Check console for details.");
+    } finally {
+      streamAbort.current = null;
+      setLoading(false);
+    }
+  };
+
+  const handleStop = () => streamAbort.current?.abort();
+
   const handleSolve = async () => {
+    if (selectedAlgorithm === "simulated_annealing") {
+      return handleSolveStream();
+    }
     setLoading(true);
     setError(null);
     try {
@@ -136,6 +185,7 @@ function App() {
 
           <ControlPanel
             datasets={datasets}
+            algorithms={algorithms}
             selectedDataset={selectedDataset}
             selectedAlgorithm={selectedAlgorithm}
             params={params}
@@ -144,6 +194,11 @@ function App() {
             onAlgorithmChange={setSelectedAlgorithm}
             onParamsChange={setParams}
             onSolve={handleSolve}
+            onStop={
+              selectedAlgorithm === "simulated_annealing"
+                ? handleStop
+                : undefined
+            }
             onBenchmark={handleBenchmark}
             pinDropMode={pinDropMode}
             onPinDropModeChange={setPinDropMode}
@@ -208,13 +263,20 @@ function App() {
 
         <div className="p-4">
           {activeTab === "itinerary" && itinerary && (
-            <ItineraryList itinerary={itinerary} />
+            <ItineraryList itinerary={itinerary} algorithms={algorithms} />
           )}
           {activeTab === "benchmark" && benchmarkResult && (
-            <BenchmarkChart result={benchmarkResult} />
+            <BenchmarkChart
+              result={benchmarkResult}
+              algorithms={algorithms}
+            />
           )}
           {activeTab === "experiment" && (
-            <ExperimentDashboard params={params} datasets={datasets} />
+            <ExperimentDashboard
+              params={params}
+              datasets={datasets}
+              algorithms={algorithms}
+            />
           )}
           {activeTab === "itinerary" && !itinerary && (
             <div className="text-center text-gray-500 mt-8">
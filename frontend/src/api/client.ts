@@ -1,10 +1,13 @@
 import axios from "axios";
 import type {
+  AlgorithmInfo,
+  ConvergencePoint,
   DatasetInfo,
   Itinerary,
   SolveParams,
   BenchmarkResult,
   ExperimentResult,
+  JobInfo,
 } from "../types";
 
 const API_BASE = "http://localhost:3000/api";
@@ -48,10 +51,17 @@ export const api = {
     }
   },
 
+  async getAlgorithms(): Promise<AlgorithmInfo[]> {
+    const response = await axios.get(`${API_BASE}/algorithms`);
+    return response.data;
+  },
+
   async solve(
     algorithm: string,
     datasetName: string,
     params: SolveParams,
+    config?: Record<string, unknown>,
+    timeLimitMs?: number,
   ): Promise<Itinerary> {
     const response = await axios.post(`${API_BASE}/solve`, {
       algorithm,
@@ -60,6 +70,8 @@ export const api = {
         name: datasetName,
       },
       params,
+      config,
+      time_limit_ms: timeLimitMs,
     });
     return response.data.itinerary;
   },
@@ -90,4 +102,88 @@ export const api = {
     });
     return response.data;
   },
+
+  async submitExperimentJob(
+    datasetName: string,
+    nRuns: number,
+    params: SolveParams,
+  ): Promise<number> {
+    const response = await axios.post(`${API_BASE}/jobs`, {
+      kind: "experiment",
+      dataset: datasetName,
+      n_runs: nRuns,
+      params,
+    });
+    return response.data.id;
+  },
+
+  async getJob<T>(id: number): Promise<JobInfo<T>> {
+    const response = await axios.get(`${API_BASE}/jobs/${id}`);
+    return response.data;
+  },
+
+  async cancelJob(id: number): Promise<JobInfo> {
+    const response = await axios.delete(`${API_BASE}/jobs/${id}`);
+    return response.data;
+  },
+
+  // Solves with simulated annealing over server-sent events, reporting
+  // progress as it arrives. Aborting `signal` stops the solve on the server.
+  async solveStream(
+    datasetName: string,
+    params: SolveParams,
+    handlers: {
+      onConvergence: (point: ConvergencePoint) => void;
+      onImproved: (itinerary: Itinerary) => void;
+    },
+    signal: AbortSignal,
+    config?: Record<string, unknown>,
+  ): Promise<Itinerary | null> {
+    const response = await fetch(`${API_BASE}/solve/stream`, {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({
+        algorithm: "simulated_annealing",
+        dataset: { type: "builtin", name: datasetName },
+        params,
+        config,
+      }),
+      signal,
+    });
+    if (!response.ok || !response.body) {
+      throw new Error(`Stream failed with status ${response.status}`);
+    }
+
+    const reader = response.body.getReader();
+    const decoder = new TextDecoder();
+    let buffer = "";
+    try {
+      for (;;) {
+        const { done, value } = await reader.read();
+        if (done) return null;
+        buffer += decoder.decode(value, { stream: true });
+
+        // Events are separated by a blank line
+        let end;
+        while ((end = buffer.indexOf("\n\n")) >= 0) {
+          const block = buffer.slice(0, end);
+          buffer = buffer.slice(end + 2);
+          let event = "message";
+          let data = "";
+          for (const line of block.split("\n")) {
+            if (line.startsWith("event:")) event = line.slice(6).trim();
+            else if (line.startsWith("data:")) data += line.slice(5).trim();
+          }
+          if (!data) continue;
+          const payload = JSON.parse(data);
+          if (event === "convergence") handlers.onConvergence(payload);
+          else if (event === "improved") handlers.onImproved(payload);
+          else if (event === "done") return payload.itinerary;
+        }
+      }
+    } catch (error) {
+      if (signal.aborted) return null;
+      throw error;
+    }
+  },
 };
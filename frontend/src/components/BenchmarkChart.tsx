@@ -9,36 +9,47 @@ import {
   ResponsiveContainer,
   Cell,
 } from "recharts";
-import type { BenchmarkResult } from "../types";
+import type { AlgorithmInfo, BenchmarkResult } from "../types";
+import { DAY_COLORS } from "../utils/colors";
+import { algorithmLabel } from "../utils/algorithms";
 
 interface BenchmarkChartProps {
   result: BenchmarkResult;
+  algorithms: AlgorithmInfo[];
 }
 
-const BenchmarkChart: React.FC<BenchmarkChartProps> = ({ result }) => {
-  const satisfactionData = [
-    {
-      name: "Greedy",
-      value: result.greedy.total_satisfaction,
-    },
-    {
-      name: "SA",
-      value: result.simulated_annealing.total_satisfaction,
-    },
-  ];
+const BenchmarkChart: React.FC<BenchmarkChartProps> = ({
+  result,
+  algorithms,
+}) => {
+  const rows = result.results.map((itinerary) => ({
+    name: algorithmLabel(algorithms, itinerary.algorithm_used),
+    itinerary,
+  }));
 
-  const timeData = [
-    {
-      name: "Greedy",
-      value: result.greedy.computation_ms,
-    },
-    {
-      name: "SA",
-      value: result.simulated_annealing.computation_ms,
-    },
-  ];
+  const satisfactionData = rows.map((row) => ({
+    name: row.name,
+    value: row.itinerary.total_satisfaction,
+  }));
 
-  const colors = ["#3B82F6", "#10B981"];
+  const timeData = rows.map((row) => ({
+    name: row.name,
+    value: row.itinerary.computation_ms,
+  }));
+
+  const colors = DAY_COLORS;
+
+  const baseline = result.results.find(
+    (itinerary) => itinerary.algorithm_used === "greedy",
+  );
+  const winner = rows.reduce<(typeof rows)[number] | undefined>(
+    (best, row) =>
+      !best ||
+      row.itinerary.total_satisfaction > best.itinerary.total_satisfaction
+        ? row
+        : best,
+    undefined,
+  );
 
   return (
     <div className="space-y-6">
@@ -54,7 +65,10 @@ const BenchmarkChart: React.FC<BenchmarkChartProps> = ({ result }) => {
             <Tooltip />
             <Bar dataKey="value" name="Satisfaction">
               {satisfactionData.map((_, index) => (
-                <Cell key={`cell-${index}`} fill={colors[index]} />
+                <Cell
+                  key={`cell-${index}`}
+                  fill={colors[index % colors.length]}
+                />
               ))}
             </Bar>
           </BarChart>
@@ -73,7 +87,10 @@ const BenchmarkChart: React.FC<BenchmarkChartProps> = ({ result }) => {
             <Tooltip />
             <Bar dataKey="value" name="Time (ms)">
               {timeData.map((_, index) => (
-                <Cell key={`cell-${index}`} fill={colors[index]} />
+                <Cell
+                  key={`cell-${index}`}
+                  fill={colors[index % colors.length]}
+                />
               ))}
             </Bar>
           </BarChart>
@@ -92,54 +109,40 @@ const BenchmarkChart: React.FC<BenchmarkChartProps> = ({ result }) => {
             </tr>
           </thead>
           <tbody>
-            <tr className="border-b">
-              <td className="py-2 font-medium">Greedy</td>
-              <td className="text-right">
-                {result.greedy.total_satisfaction.toFixed(2)}
-              </td>
-              <td className="text-right">{result.greedy.total_attractions}</td>
-              <td className="text-right">
-                ${result.greedy.total_cost.toFixed(2)}
-              </td>
-              <td className="text-right">{result.greedy.computation_ms}</td>
-            </tr>
-            <tr>
-              <td className="py-2 font-medium">SA</td>
-              <td className="text-right">
-                {result.simulated_annealing.total_satisfaction.toFixed(2)}
-              </td>
-              <td className="text-right">
-                {result.simulated_annealing.total_attractions}
-              </td>
-              <td className="text-right">
-                ${result.simulated_annealing.total_cost.toFixed(2)}
-              </td>
-              <td className="text-right">
-                {result.simulated_annealing.computation_ms}
-              </td>
-            </tr>
+            {rows.map((row) => (
+              <tr key={row.itinerary.algorithm_used} className="border-b">
+                <td className="py-2 font-medium">{row.name}</td>
+                <td className="text-right">
+                  {row.itinerary.total_satisfaction.toFixed(2)}
+                </td>
+                <td className="text-right">
+                  {row.itinerary.total_attractions}
+                </td>
+                <td className="text-right">
+                  ${row.itinerary.total_cost.toFixed(2)}
+                </td>
+                <td className="text-right">{row.itinerary.computation_ms}</td>
+              </tr>
+            ))}
           </tbody>
         </table>
 
-        <div className="mt-4 text-center">
-          {result.simulated_annealing.total_satisfaction >
-          result.greedy.total_satisfaction ? (
+        {winner && (
+          <div className="mt-4 text-center">
             <span className="inline-flex items-center px-3 py-1 rounded-full text-sm font-medium bg-green-100 text-green-800">
-              🏆 SA wins with{" "}
-              {(
-                (result.simulated_annealing.total_satisfaction /
-                  result.greedy.total_satisfaction -
-                  1) *
-                100
-              ).toFixed(1)}
-              % improvement
-            </span>
-          ) : (
-            <span className="inline-flex items-center px-3 py-1 rounded-full text-sm font-medium bg-blue-100 text-blue-800">
-              🏆 Greedy wins
+              🏆 {winner.name} wins
+              {baseline &&
+                baseline !== winner.itinerary &&
+                baseline.total_satisfaction > 0 &&
+                ` with ${(
+                  (winner.itinerary.total_satisfaction /
+                    baseline.total_satisfaction -
+                    1) *
+                  100
+                ).toFixed(1)}% improvement over greedy`}
             </span>
-          )}
-        </div>
+          </div>
+        )}
       </div>
     </div>
   );
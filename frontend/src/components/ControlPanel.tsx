@@ -1,8 +1,9 @@
 import React from "react";
-import type { DatasetInfo, SolveParams } from "../types";
+import type { AlgorithmInfo, DatasetInfo, SolveParams } from "../types";
 
 interface ControlPanelProps {
   datasets: DatasetInfo[];
+  algorithms: AlgorithmInfo[];
   selectedDataset: string;
   selectedAlgorithm: string;
   params: SolveParams;
@@ -11,6 +12,8 @@ interface ControlPanelProps {
   onAlgorithmChange: (algorithm: string) => void;
   onParamsChange: (params: SolveParams) => void;
   onSolve: () => void;
+  // Stops a streaming solve, keeping the best plan so far
+  onStop?: () => void;
   onBenchmark: () => void;
   pinDropMode?: "start" | "end" | null;
   onPinDropModeChange?: (mode: "start" | "end" | null) => void;
@@ -18,6 +21,7 @@ interface ControlPanelProps {
 
 const ControlPanel: React.FC<ControlPanelProps> = ({
   datasets,
+  algorithms,
   selectedDataset,
   selectedAlgorithm,
   params,
@@ -26,10 +30,14 @@ const ControlPanel: React.FC<ControlPanelProps> = ({
   onAlgorithmChange,
   onParamsChange,
   onSolve,
+  onStop,
   onBenchmark,
   pinDropMode,
   onPinDropModeChange,
 }) => {
+  const datasetSize =
+    datasets.find((ds) => ds.name === selectedDataset)?.size ?? 0;
+
   return (
     <div className="space-y-6">
       {/* Algorithm Selection */}
@@ -43,8 +51,19 @@ const ControlPanel: React.FC<ControlPanelProps> = ({
           className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
           disabled={loading}
         >
-          <option value="greedy">Greedy (Baseline)</option>
-          <option value="simulated_annealing">Simulated Annealing</option>
+          {algorithms.map((algo) => {
+            const max = algo.capabilities.max_attractions;
+            return (
+              <option
+                key={algo.name}
+                value={algo.name}
+                disabled={max != null && datasetSize > max}
+              >
+                {algo.label}
+                {max != null ? ` (up to ${max} attractions)` : ""}
+              </option>
+            );
+          })}
         </select>
       </div>
 
@@ -234,6 +253,15 @@ const ControlPanel: React.FC<ControlPanelProps> = ({
           {loading ? "Solving..." : "Solve"}
         </button>
 
+        {loading && onStop && (
+          <button
+            onClick={onStop}
+            className="w-full bg-gray-200 hover:bg-gray-300 text-gray-800 font-medium py-2 px-4 rounded-md transition-colors"
+          >
+            Stop
+          </button>
+        )}
+
         <button
           onClick={onBenchmark}
           disabled={loading}
@@ -49,6 +49,17 @@ const ConvergencePlot: React.FC<ConvergencePlotProps> = ({ data }) => {
             dot={false}
             name="Satisfaction"
           />
+          {data.some((point) => point.mean_fitness !== undefined) && (
+            <Line
+              yAxisId="left"
+              type="monotone"
+              dataKey="mean_fitness"
+              stroke="#10B981"
+              strokeWidth={2}
+              dot={false}
+              name="Population mean"
+            />
+          )}
           <Line
             yAxisId="right"
             type="monotone"
@@ -1,4 +1,4 @@
-import React, { useState } from "react";
+import React, { useRef, useState } from "react";
 import {
   BarChart,
   Bar,
@@ -12,44 +12,86 @@ import {
   Cell,
 } from "recharts";
 import { api } from "../api/client";
-import type { DatasetInfo, SolveParams, ExperimentResult } from "../types";
+import type {
+  AlgorithmInfo,
+  DatasetInfo,
+  SolveParams,
+  ExperimentResult,
+} from "../types";
+import { DAY_COLORS } from "../utils/colors";
+import { algorithmLabel } from "../utils/algorithms";
 
 interface ExperimentDashboardProps {
   params: SolveParams;
   datasets: DatasetInfo[];
+  algorithms: AlgorithmInfo[];
 }
 
 const ExperimentDashboard: React.FC<ExperimentDashboardProps> = ({
   params,
   datasets,
+  algorithms,
 }) => {
   const [nRuns, setNRuns] = useState(10);
   const [loading, setLoading] = useState(false);
   const [results, setResults] = useState<ExperimentResult[]>([]);
+  const [progress, setProgress] = useState({ done: 0, total: 0 });
+  const jobId = useRef<number | null>(null);
+  const cancelled = useRef(false);
 
+  // Runs each dataset as a background job and polls it for progress
   const runExperiments = async () => {
     setLoading(true);
     setResults([]);
+    cancelled.current = false;
 
     try {
       for (const dataset of datasets) {
-        const result = await api.experiment(dataset.name, nRuns, params);
-        setResults((prev) => [...prev, result]);
+        if (cancelled.current) break;
+        const id = await api.submitExperimentJob(dataset.name, nRuns, params);
+        jobId.current = id;
+
+        let job = await api.getJob<ExperimentResult>(id);
+        while (job.status === "queued" || job.status === "running") {
+          setProgress({ done: job.done, total: job.total });
+          await new Promise((resolve) => setTimeout(resolve, 500));
+          job = await api.getJob<ExperimentResult>(id);
+        }
+        if (job.status === "completed" && job.result) {
+          const result = job.result;
+          setResults((prev) => [...prev, result]);
+        } else if (job.status === "failed") {
+          console.error("Experiment failed:", job.error);
+        }
       }
     } catch (error) {
       console.error("Failed to run experiments:", error);
     } finally {
+      jobId.current = null;
       setLoading(false);
     }
   };
 
-  const chartData = results.map((r) => ({
-    name: r.dataset_name,
-    greedy: r.greedy.mean_satisfaction,
-    greedyError: r.greedy.std_satisfaction,
-    sa: r.sa.mean_satisfaction,
-    saError: r.sa.std_satisfaction,
-  }));
+  const cancelExperiments = async () => {
+    cancelled.current = true;
+    if (jobId.current !== null) {
+      await api.cancelJob(jobId.current);
+    }
+  };
+
+  const chartData = results.map((r) => {
+    const row: Record<string, string | number> = { name: r.dataset_name };
+    for (const stats of r.algorithms) {
+      row[stats.algorithm] = stats.mean_satisfaction;
+      row[`${stats.algorithm}Error`] = stats.std_satisfaction;
+    }
+    return row;
+  });
+
+  // Every algorithm that appears in any result, in server order
+  const shown = Array.from(
+    new Set(results.flatMap((r) => r.algorithms.map((a) => a.algorithm))),
+  );
 
   return (
     <div className="space-y-4">
@@ -78,9 +120,17 @@ const ExperimentDashboard: React.FC<ExperimentDashboardProps> = ({
           className="w-full bg-purple-500 hover:bg-purple-600 text-white font-medium py-2 px-4 rounded-md disabled:bg-gray-400 disabled:cursor-not-allowed transition-colors"
         >
           {loading
-            ? `Running... (${results.length}/${datasets.length})`
+            ? `Running... (${results.length}/${datasets.length} datasets, ${progress.done}/${progress.total} runs)`
             : "Run All Experiments"}
         </button>
+        {loading && (
+          <button
+            onClick={cancelExperiments}
+            className="w-full mt-2 bg-gray-200 hover:bg-gray-300 text-gray-800 font-medium py-2 px-4 rounded-md transition-colors"
+          >
+            Cancel
+          </button>
+        )}
       </div>
 
       {/* Chart */}
@@ -97,12 +147,20 @@ const ExperimentDashboard: React.FC<ExperimentDashboardProps> = ({
                 <YAxis />
                 <Tooltip />
                 <Legend />
-                <Bar dataKey="greedy" fill="#3B82F6" name="Greedy">
-                  <ErrorBar dataKey="greedyError" width={4} strokeWidth={2} />
-                </Bar>
-                <Bar dataKey="sa" fill="#10B981" name="SA">
-                  <ErrorBar dataKey="saError" width={4} strokeWidth={2} />
-                </Bar>
+                {shown.map((name, index) => (
+                  <Bar
+                    key={name}
+                    dataKey={name}
+                    fill={DAY_COLORS[index % DAY_COLORS.length]}
+                    name={algorithmLabel(algorithms, name)}
+                  >
+                    <ErrorBar
+                      dataKey={`${name}Error`}
+                      width={4}
+                      strokeWidth={2}
+                    />
+                  </Bar>
+                ))}
               </BarChart>
             </ResponsiveContainer>
           </div>
@@ -116,80 +174,99 @@ const ExperimentDashboard: React.FC<ExperimentDashboardProps> = ({
                     <th className="px-4 py-2 text-left font-medium text-gray-700">
                       Dataset
                     </th>
-                    <th className="px-4 py-2 text-right font-medium text-gray-700">
-                      Size
-                    </th>
-                    <th
-                      className="px-4 py-2 text-center font-medium text-gray-700"
-                      colSpan={2}
-                    >
-                      Greedy
+                    <th className="px-4 py-2 text-left font-medium text-gray-700">
+                      Algorithm
                     </th>
-                    <th
-                      className="px-4 py-2 text-center font-medium text-gray-700"
-                      colSpan={2}
-                    >
-                      SA
+                    <th className="px-2 py-2 text-right font-medium text-gray-700">
+                      Sat (μ±σ)
                     </th>
-                    <th className="px-4 py-2 text-center font-medium text-gray-700">
-                      Improvement
+                    <th className="px-2 py-2 text-right font-medium text-gray-700">
+                      Median [IQR]
                     </th>
-                  </tr>
-                  <tr className="border-t">
-                    <th></th>
-                    <th></th>
-                    <th className="px-2 py-1 text-xs text-gray-600">
-                      Sat (μ±σ)
+                    <th className="px-2 py-2 text-right font-medium text-gray-700">
+                      95% CI
                     </th>
-                    <th className="px-2 py-1 text-xs text-gray-600">
+                    <th className="px-2 py-2 text-right font-medium text-gray-700">
                       Time (ms)
                     </th>
-                    <th className="px-2 py-1 text-xs text-gray-600">
-                      Sat (μ±σ)
+                    <th className="px-2 py-2 text-right font-medium text-gray-700">
+                      vs Greedy
                     </th>
-                    <th className="px-2 py-1 text-xs text-gray-600">
-                      Time (ms)
+                    <th className="px-2 py-2 text-right font-medium text-gray-700">
+                      p
                     </th>
-                    <th className="px-2 py-1 text-xs text-gray-600">(%)</th>
                   </tr>
                 </thead>
                 <tbody className="divide-y">
-                  {results.map((result) => (
-                    <tr key={result.dataset_name} className="hover:bg-gray-50">
-                      <td className="px-4 py-2 font-medium">
-                        {result.dataset_name}
-                      </td>
-                      <td className="px-4 py-2 text-right">
-                        {result.dataset_size}
-                      </td>
-                      <td className="px-2 py-2 text-right">
-                        {result.greedy.mean_satisfaction.toFixed(2)} ±{" "}
-                        {result.greedy.std_satisfaction.toFixed(2)}
-                      </td>
-                      <td className="px-2 py-2 text-right">
-                        {result.greedy.mean_ms.toFixed(1)}
-                      </td>
-                      <td className="px-2 py-2 text-right">
-                        {result.sa.mean_satisfaction.toFixed(2)} ±{" "}
-                        {result.sa.std_satisfaction.toFixed(2)}
-                      </td>
-                      <td className="px-2 py-2 text-right">
-                        {result.sa.mean_ms.toFixed(1)}
-                      </td>
-                      <td className="px-2 py-2 text-right">
-                        <span
-                          className={
-                            result.sa_improvement_pct > 0
-                              ? "text-green-600 font-medium"
-                              : "text-gray-600"
+                  {results.flatMap((result) =>
+                    result.algorithms.map((stats, index) => (
+                      <tr
+                        key={`${result.dataset_name}-${stats.algorithm}`}
+                        className="hover:bg-gray-50"
+                      >
+                        <td className="px-4 py-2 font-medium">
+                          {index === 0
+                            ? `${result.dataset_name} (${result.dataset_size})`
+                            : ""}
+                        </td>
+                        <td className="px-4 py-2">
+                          {algorithmLabel(algorithms, stats.algorithm)}
+                        </td>
+                        <td className="px-2 py-2 text-right">
+                          {stats.mean_satisfaction.toFixed(2)} ±{" "}
+                          {stats.std_satisfaction.toFixed(2)}
+                        </td>
+                        <td className="px-2 py-2 text-right">
+                          {stats.satisfaction.median.toFixed(2)} [
+                          {stats.satisfaction.q1.toFixed(2)}–
+                          {stats.satisfaction.q3.toFixed(2)}]
+                        </td>
+                        <td className="px-2 py-2 text-right">
+                          {stats.satisfaction.ci95_low.toFixed(2)}–
+                          {stats.satisfaction.ci95_high.toFixed(2)}
+                        </td>
+                        <td className="px-2 py-2 text-right">
+                          {stats.mean_ms.toFixed(1)}
+                        </td>
+                        <td className="px-2 py-2 text-right">
+                          <span
+                            className={
+                              stats.improvement_pct > 0
+                                ? "text-green-600 font-medium"
+                                : "text-gray-600"
+                            }
+                          >
+                            {stats.improvement_pct > 0 ? "+" : ""}
+                            {stats.improvement_pct.toFixed(1)}%
+                          </span>
+                        </td>
+                        <td
+                          className="px-2 py-2 text-right"
+                          title={
+                            stats.vs_greedy
+                              ? `Wilcoxon signed-rank, ${stats.vs_greedy.n} non-tied pairs`
+                              : undefined
                           }
                         >
-                          {result.sa_improvement_pct > 0 ? "+" : ""}
-                          {result.sa_improvement_pct.toFixed(1)}%
-                        </span>
-                      </td>
-                    </tr>
-                  ))}
+                          {stats.vs_greedy ? (
+                            <span
+                              className={
+                                stats.vs_greedy.p_value < 0.05
+                                  ? "font-medium"
+                                  : "text-gray-500"
+                              }
+                            >
+                              {stats.vs_greedy.p_value < 0.001
+                                ? "<0.001"
+                                : stats.vs_greedy.p_value.toFixed(3)}
+                            </span>
+                          ) : (
+                            "—"
+                          )}
+                        </td>
+                      </tr>
+                    )),
+                  )}
                 </tbody>
               </table>
             </div>
@@ -198,10 +275,28 @@ const ExperimentDashboard: React.FC<ExperimentDashboardProps> = ({
           {/* Valid Runs Info */}
           <div className="bg-blue-50 border border-blue-200 rounded-lg p-3">
             <div className="text-sm text-blue-800">
-              <strong>Valid Runs:</strong> All results show{" "}
-              {results[0]?.greedy.valid_runs}/{nRuns} valid runs for both
-              algorithms
+              <strong>Valid Runs:</strong>{" "}
+              {results
+                .flatMap((r) => r.algorithms)
+                .every((stats) => stats.valid_runs === nRuns)
+                ? `All algorithms produced ${nRuns}/${nRuns} valid runs`
+                : "Some runs failed verification"}
             </div>
+            <ul className="mt-1 text-xs text-red-700 space-y-0.5">
+              {results.flatMap((result) =>
+                result.algorithms.flatMap((stats) =>
+                  stats.invalid_runs.map((run) => (
+                    <li
+                      key={`${result.dataset_name}-${stats.algorithm}-${run.seed}`}
+                    >
+                      {result.dataset_name} /{" "}
+                      {algorithmLabel(algorithms, stats.algorithm)} seed{" "}
+                      {run.seed}: {run.error}
+                    </li>
+                  )),
+                ),
+              )}
+            </ul>
           </div>
         </>
       )}
@@ -1,13 +1,18 @@
 import React, { useState } from "react";
-import type { Itinerary } from "../types";
+import type { AlgorithmInfo, Itinerary } from "../types";
 import { getDayColor, getCategoryIcon, formatTime } from "../utils/colors";
+import { algorithmLabel } from "../utils/algorithms";
 import ConvergencePlot from "./ConvergencePlot";
 
 interface ItineraryListProps {
   itinerary: Itinerary;
+  algorithms: AlgorithmInfo[];
 }
 
-const ItineraryList: React.FC<ItineraryListProps> = ({ itinerary }) => {
+const ItineraryList: React.FC<ItineraryListProps> = ({
+  itinerary,
+  algorithms,
+}) => {
   const [expandedDays, setExpandedDays] = useState<Set<number>>(new Set([1]));
 
   const toggleDay = (day: number) => {
@@ -49,9 +54,7 @@ const ItineraryList: React.FC<ItineraryListProps> = ({ itinerary }) => {
       {/* Algorithm Badge */}
       <div className="flex items-center justify-between">
         <span className="inline-flex items-center px-3 py-1 rounded-full text-sm font-medium bg-blue-100 text-blue-800">
-          {itinerary.algorithm_used === "greedy"
-            ? "Greedy Algorithm"
-            : "Simulated Annealing"}
+          {algorithmLabel(algorithms, itinerary.algorithm_used)}
         </span>
         <span className="text-sm text-gray-500">
           {itinerary.computation_ms}ms
@@ -141,6 +144,21 @@ const ItineraryList: React.FC<ItineraryListProps> = ({ itinerary }) => {
                     </div>
                   </div>
                 ))}
+                {day.appointments?.map((appointment) => (
+                  <div key={appointment.start} className="text-xs text-gray-600 px-3">
+                    📅 {appointment.name} {formatTime(appointment.start)} -{" "}
+                    {formatTime(appointment.end)} by {appointment.leg.mode} (
+                    {appointment.leg.travel_time} min, $
+                    {appointment.leg.fare.toFixed(2)})
+                  </div>
+                ))}
+                {day.return_leg && day.return_leg.travel_time > 0 && day.return_arrival_time != null && (
+                  <div className="text-xs text-gray-600 px-3">
+                    🏨 Back at {formatTime(day.return_arrival_time)} by{" "}
+                    {day.return_leg.mode} ({day.return_leg.travel_time} min, $
+                    {day.return_leg.fare.toFixed(2)})
+                  </div>
+                )}
               </div>
             )}
           </div>
@@ -3,6 +3,30 @@ export interface Location {
   lng: number;
 }
 
+export type TransportMode = "Walk" | "Taxi" | "Rail" | "Boat";
+
+export interface Leg {
+  mode: TransportMode;
+  travel_time: number;
+  fare: number;
+}
+
+export interface ModeProfile {
+  mode: TransportMode;
+  speed_kmh?: number;
+  access_minutes?: number;
+  base_fare?: number;
+  fare_per_km?: number;
+  max_distance_km?: number;
+  available_from?: number;
+  available_until?: number;
+}
+
+export interface TransportOptions {
+  modes: ModeProfile[];
+  value_of_time?: number;
+}
+
 export interface Visit {
   attraction_id: number;
   attraction_name: string;
@@ -11,20 +35,35 @@ export interface Visit {
   fee: number;
   preference: number;
   category: string;
+  leg: Leg;
+  wait_minutes: number;
+}
+
+export interface AppointmentVisit {
+  name: string;
+  start: number;
+  end: number;
+  leg: Leg;
+  arrival_time: number;
 }
 
 export interface DayPlan {
   day: number;
   visits: Visit[];
+  appointments?: AppointmentVisit[];
   total_travel_time: number;
   total_cost: number;
+  total_fare: number;
   total_satisfaction: number;
+  return_leg?: Leg | null;
+  return_arrival_time?: number | null;
 }
 
 export interface ConvergencePoint {
   iteration: number;
   satisfaction: number;
   temperature: number;
+  mean_fitness?: number;
 }
 
 export interface Itinerary {
@@ -46,6 +85,32 @@ export interface SolveParams {
   hotel_lng: number;
   end_lat?: number;
   end_lng?: number;
+  transport?: TransportOptions;
+  start_date?: string; // YYYY-MM-DD
+  holidays?: string[];
+  day_overrides?: DayOverride[];
+}
+
+export interface Appointment {
+  name: string;
+  lat: number;
+  lng: number;
+  start: number;
+  end: number;
+}
+
+export interface DayOverride {
+  day: number;
+  hotel_lat?: number;
+  hotel_lng?: number;
+  start_time?: number;
+  daily_time_budget?: number;
+  start_lat?: number;
+  start_lng?: number;
+  end_lat?: number;
+  end_lng?: number;
+  open_ended?: boolean;
+  appointments?: Appointment[];
 }
 
 export interface DatasetInfo {
@@ -53,24 +118,120 @@ export interface DatasetInfo {
   size: number;
 }
 
+export interface Capabilities {
+  stochastic: boolean;
+  exact: boolean;
+  convergence_data: boolean;
+  max_attractions?: number | null;
+}
+
+export interface AlgorithmInfo {
+  name: string;
+  label: string;
+  capabilities: Capabilities;
+  default_config: Record<string, unknown>;
+}
+
+export type CoolingSchedule =
+  | { type: "geometric"; rate: number }
+  | { type: "linear" }
+  | {
+      type: "adaptive";
+      rate: number;
+      reheat_after: number;
+      reheat_factor: number;
+    };
+
+export interface SaConfig {
+  initial_temp?: number;
+  min_temp?: number;
+  max_iterations?: number;
+  cooling?: CoolingSchedule;
+  time_limit_ms?: number | null;
+  penalties?: {
+    duplicate?: number;
+    no_transport?: number;
+    closed?: number;
+    over_budget?: number;
+    over_time?: number;
+  };
+  chains?: number;
+}
+
 export interface BenchmarkResult {
-  greedy: Itinerary;
-  simulated_annealing: Itinerary;
+  results: Itinerary[];
 }
 
 export interface AlgorithmStats {
+  algorithm: string;
   mean_satisfaction: number;
   std_satisfaction: number;
   mean_ms: number;
   valid_runs: number;
   mean_attractions: number;
   mean_cost: number;
+  optimality_gap_pct?: number;
+  improvement_pct: number;
+  satisfaction: Summary;
+  invalid_runs: RunOutcome[];
+  vs_greedy?: WilcoxonResult;
+}
+
+export interface Summary {
+  min: number;
+  q1: number;
+  median: number;
+  q3: number;
+  max: number;
+  ci95_low: number;
+  ci95_high: number;
+}
+
+export interface RunOutcome {
+  seed: number;
+  satisfaction: number;
+  ms: number;
+  error?: string;
+}
+
+export interface WilcoxonResult {
+  n: number;
+  w_plus: number;
+  w_minus: number;
+  p_value: number;
+  exact: boolean;
+}
+
+export interface ExactStats {
+  satisfaction: number;
+  upper_bound: number;
+  proven_optimal: boolean;
+  nodes: number;
+  ms: number;
 }
 
 export interface ExperimentResult {
   dataset_name: string;
   dataset_size: number;
-  greedy: AlgorithmStats;
-  sa: AlgorithmStats;
-  sa_improvement_pct: number;
+  algorithms: AlgorithmStats[];
+  exact?: ExactStats;
+}
+
+export type JobStatus =
+  | "queued"
+  | "running"
+  | "completed"
+  | "failed"
+  | "cancelled";
+
+export interface JobInfo<T = unknown> {
+  id: number;
+  kind: "solve" | "experiment";
+  status: JobStatus;
+  done: number;
+  total: number;
+  created_at: number;
+  finished_at?: number;
+  result?: T;
+  error?: string;
 }
@@ -0,0 +1,8 @@
+import type { AlgorithmInfo } from "../types";
+
+export const algorithmLabel = (
+  algorithms: AlgorithmInfo[],
+  name: string,
+): string => {
+  return algorithms.find((algo) => algo.name === name)?.label ?? name;
+};